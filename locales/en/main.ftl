@@ -0,0 +1,155 @@
+## Layout
+
+site-description = Personal blog and project showcase
+nav-home = Home
+nav-blog = Blog
+nav-projects = Projects
+nav-resume = Resume
+nav-contact = Contact
+nav-search = Search
+back-to-top = Back to top
+footer-built-with = Built with Rust, Axum, and HTMX.
+footer-languages = Language
+rss-feed = RSS Feed
+atom-feed = Atom Feed
+json-feed = JSON Feed
+tag-feed = RSS: posts tagged "{ $tag }"
+projects-feed = RSS: projects
+breadcrumb = Breadcrumb
+
+## Home page
+
+home-title = Software Engineer
+home-hero-blog = Personal blog
+home-hero-projects = project showcase
+home-hero-subtitle = Scala, Java, Rust, distributed systems, and functional programming
+home-recent-posts = Recent Posts
+home-featured-projects = Featured Projects
+view-all-posts = View all posts
+view-all-projects = View all projects
+
+## Blog
+
+blog-title = Blog
+blog-empty = No posts yet. Check back soon!
+blog-browse-tags = Browse by tag
+blog-archive = Archive
+back-to-blog = Back to blog
+post-updated = Updated { $date }
+post-history = History
+history-title = History of “{ $title }”
+history-empty = No recorded changes for this post.
+back-to-post = Back to post
+post-default-description = Blog post
+reading-time = { $minutes } min read
+share = Share:
+share-on = Share on { $network }
+webmentions-title = Webmentions
+webmentions-likes = Likes
+webmentions-reposts = Reposts
+webmentions-replies = Replies
+webmentions-mentions = Mentioned by
+
+## Tags
+
+tags-title = Tags
+tags-description = All blog tags
+tags-all = All tags
+tags-empty = No tags yet.
+tag-title = Posts tagged "{ $tag }"
+tag-empty = No posts with this tag.
+
+## Archive
+
+archive-title = Archive
+archive-description = Blog archive: { $title }
+month-1 = January
+month-2 = February
+month-3 = March
+month-4 = April
+month-5 = May
+month-6 = June
+month-7 = July
+month-8 = August
+month-9 = September
+month-10 = October
+month-11 = November
+month-12 = December
+
+## Authors
+
+author-posts = Posts
+author-posts-by = Posts by { $name }
+author-empty = No posts yet.
+
+## Pagination
+
+pagination = Pagination
+pagination-prev = Previous page
+pagination-status = Page { $page } of { $total }
+load-more = Load more
+
+## Projects
+
+projects-title = Projects
+projects-empty = No projects yet. Check back soon!
+project-default-description = Project
+project-live-demo = Live Demo
+back-to-projects = Back to projects
+status-active = Active
+status-completed = Completed
+status-archived = Archived
+status-planned = Planned
+
+## Collections
+
+collection-empty = Nothing here yet. Check back soon!
+back-to-collection = Back to { $title }
+
+## Search
+
+search-title = Search
+search-description = Search posts and projects
+search-placeholder = Search posts and projects...
+search-button = Search
+search-nothing-found = Nothing found for "{ $query }".
+search-kind-post = Post
+search-kind-project = Project
+
+## Contact
+
+contact-title = Contact
+contact-intro = Have a question or want to work together? Send me a message and I'll get back to you.
+contact-name = Name
+contact-name-placeholder = Your name
+contact-email = Email
+contact-subject = Subject
+contact-subject-placeholder = What's this about?
+contact-message = Message
+contact-message-placeholder = Your message...
+contact-send = Send Message
+contact-direct = Or reach out directly at
+contact-sent-title = Message Sent
+contact-sent-heading = Message Sent!
+contact-sent-body = Thank you for reaching out. I'll get back to you as soon as possible.
+back-to-home = Back to home
+contact-error-captcha-missing = Please complete the CAPTCHA verification
+contact-error-captcha-failed = CAPTCHA verification failed. Please try again.
+contact-error-captcha-unavailable = CAPTCHA verification error. Please try again.
+contact-error-name = Name is required
+contact-error-email = Valid email is required
+contact-error-message = Message is required
+contact-error-send = Failed to send message. Please try again later.
+
+## Resume
+
+resume-title = Resume
+resume-summary = Summary
+resume-skills = Technical Skills
+resume-experience = Experience
+resume-education = Education
+resume-certificates = Certifications
+resume-projects = Open Source
+resume-present = Present
+resume-print = Print version
+resume-vcard = Add to contacts
@@ -0,0 +1,155 @@
+## Layout
+
+site-description = Личный блог и витрина проектов
+nav-home = Главная
+nav-blog = Блог
+nav-projects = Проекты
+nav-resume = Резюме
+nav-contact = Контакты
+nav-search = Поиск
+back-to-top = Наверх
+footer-built-with = Сделано на Rust, Axum и HTMX.
+footer-languages = Язык
+rss-feed = RSS-лента
+atom-feed = Atom-лента
+json-feed = JSON-лента
+tag-feed = RSS: записи с тегом «{ $tag }»
+projects-feed = RSS: проекты
+breadcrumb = Навигация
+
+## Home page
+
+home-title = Программист
+home-hero-blog = Личный блог
+home-hero-projects = витрина проектов
+home-hero-subtitle = Scala, Java, Rust, распределённые системы и функциональное программирование
+home-recent-posts = Свежие записи
+home-featured-projects = Избранные проекты
+view-all-posts = Все записи
+view-all-projects = Все проекты
+
+## Blog
+
+blog-title = Блог
+blog-empty = Записей пока нет. Загляните позже!
+blog-browse-tags = Теги
+blog-archive = Архив
+back-to-blog = Назад в блог
+post-updated = Обновлено { $date }
+post-history = История
+history-title = История «{ $title }»
+history-empty = Для этой записи нет истории изменений.
+back-to-post = Назад к записи
+post-default-description = Запись в блоге
+reading-time = { $minutes } мин чтения
+share = Поделиться:
+share-on = Поделиться в { $network }
+webmentions-title = Упоминания
+webmentions-likes = Отметки «нравится»
+webmentions-reposts = Репосты
+webmentions-replies = Ответы
+webmentions-mentions = Упомянули
+
+## Tags
+
+tags-title = Теги
+tags-description = Все теги блога
+tags-all = Все теги
+tags-empty = Тегов пока нет.
+tag-title = Записи с тегом «{ $tag }»
+tag-empty = Записей с этим тегом нет.
+
+## Archive
+
+archive-title = Архив
+archive-description = Архив блога: { $title }
+month-1 = Январь
+month-2 = Февраль
+month-3 = Март
+month-4 = Апрель
+month-5 = Май
+month-6 = Июнь
+month-7 = Июль
+month-8 = Август
+month-9 = Сентябрь
+month-10 = Октябрь
+month-11 = Ноябрь
+month-12 = Декабрь
+
+## Authors
+
+author-posts = Записи
+author-posts-by = Записи автора { $name }
+author-empty = Записей пока нет.
+
+## Pagination
+
+pagination = Страницы
+pagination-prev = Предыдущая страница
+pagination-status = Страница { $page } из { $total }
+load-more = Показать ещё
+
+## Projects
+
+projects-title = Проекты
+projects-empty = Проектов пока нет. Загляните позже!
+project-default-description = Проект
+project-live-demo = Демо
+back-to-projects = Назад к проектам
+status-active = В работе
+status-completed = Завершён
+status-archived = В архиве
+status-planned = В планах
+
+## Collections
+
+collection-empty = Здесь пока пусто. Загляните позже!
+back-to-collection = Назад: { $title }
+
+## Search
+
+search-title = Поиск
+search-description = Поиск по записям и проектам
+search-placeholder = Искать в записях и проектах...
+search-button = Найти
+search-nothing-found = По запросу «{ $query }» ничего не найдено.
+search-kind-post = Запись
+search-kind-project = Проект
+
+## Contact
+
+contact-title = Контакты
+contact-intro = Есть вопрос или идея совместного проекта? Напишите мне, и я отвечу.
+contact-name = Имя
+contact-name-placeholder = Ваше имя
+contact-email = Email
+contact-subject = Тема
+contact-subject-placeholder = О чём письмо?
+contact-message = Сообщение
+contact-message-placeholder = Ваше сообщение...
+contact-send = Отправить
+contact-direct = Или напишите напрямую на
+contact-sent-title = Сообщение отправлено
+contact-sent-heading = Сообщение отправлено!
+contact-sent-body = Спасибо, что написали. Я отвечу как можно скорее.
+back-to-home = На главную
+contact-error-captcha-missing = Пройдите проверку CAPTCHA
+contact-error-captcha-failed = Проверка CAPTCHA не пройдена. Попробуйте ещё раз.
+contact-error-captcha-unavailable = Ошибка проверки CAPTCHA. Попробуйте ещё раз.
+contact-error-name = Укажите имя
+contact-error-email = Укажите корректный email
+contact-error-message = Напишите сообщение
+contact-error-send = Не удалось отправить сообщение. Попробуйте позже.
+
+## Resume
+
+resume-title = Резюме
+resume-summary = Кратко
+resume-skills = Технические навыки
+resume-experience = Опыт работы
+resume-education = Образование
+resume-certificates = Сертификаты
+resume-projects = Открытые проекты
+resume-present = по настоящее время
+resume-print = Версия для печати
+resume-vcard = Добавить в контакты
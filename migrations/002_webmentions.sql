@@ -0,0 +1,24 @@
+-- Verified Webmentions; shown under posts once approved
+
+CREATE TABLE IF NOT EXISTS webmentions (
+    id SERIAL PRIMARY KEY,
+    source VARCHAR(2048) NOT NULL,
+    target VARCHAR(2048) NOT NULL,
+    -- Content store key of the mentioned post
+    post_key VARCHAR(512) NOT NULL,
+    -- like, repost, reply or mention
+    kind VARCHAR(16) NOT NULL,
+    author_name VARCHAR(255),
+    author_url VARCHAR(2048),
+    author_photo VARCHAR(2048),
+    url VARCHAR(2048),
+    content TEXT,
+    published TIMESTAMPTZ,
+    -- pending, approved or rejected
+    status VARCHAR(16) NOT NULL DEFAULT 'pending',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (source, target)
+);
+
+CREATE INDEX idx_webmentions_post_key ON webmentions(post_key, status);
@@ -0,0 +1,155 @@
+//! Content-hashed URLs for files under `static/`.
+//!
+//! The manifest is built once at startup: `css/style.css` is also served as
+//! `css/style.<hash>.css`, where the hash covers the file's contents. Templates
+//! link to the hashed name through [`url`], so those URLs can be cached
+//! forever and change whenever the file does. Edits to `static/` need a
+//! restart to get a new hash.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
+
+/// Hex characters of the content hash kept in file names
+const HASH_LEN: usize = 8;
+
+static MANIFEST: OnceLock<AssetManifest> = OnceLock::new();
+
+/// Logical paths of static files (relative to `static/`) and their hashed names
+#[derive(Debug, Default)]
+pub struct AssetManifest {
+    hashed: HashMap<String, String>,
+    logical: HashMap<String, String>,
+}
+
+impl AssetManifest {
+    /// Hash every file under `dir`
+    pub fn build(dir: &Path) -> Result<Self> {
+        let mut manifest = Self::default();
+        manifest.add_dir(dir, dir)?;
+        Ok(manifest)
+    }
+
+    fn add_dir(&mut self, root: &Path, dir: &Path) -> Result<()> {
+        for entry in fs::read_dir(dir).with_context(|| format!("Failed to read {:?}", dir))? {
+            let entry = entry?;
+            let path = entry.path();
+            if entry.file_type()?.is_dir() {
+                self.add_dir(root, &path)?;
+                continue;
+            }
+
+            let Some(logical) = path
+                .strip_prefix(root)
+                .ok()
+                .and_then(|p| p.to_str())
+                .map(|p| p.replace('\\', "/"))
+            else {
+                continue;
+            };
+            let contents = fs::read(&path).with_context(|| format!("Failed to read {:?}", path))?;
+            let hash = hex::encode(Sha256::digest(&contents));
+            self.insert(logical, &hash[..HASH_LEN]);
+        }
+        Ok(())
+    }
+
+    fn insert(&mut self, logical: String, hash: &str) {
+        let hashed = hashed_name(&logical, hash);
+        self.logical.insert(hashed.clone(), logical.clone());
+        self.hashed.insert(logical, hashed);
+    }
+
+    /// Site URL of a static file; files missing from the manifest keep their
+    /// plain name
+    pub fn url(&self, path: &str) -> String {
+        let path = path.trim_start_matches('/');
+        format!(
+            "/static/{}",
+            self.hashed.get(path).map_or(path, String::as_str)
+        )
+    }
+
+    /// Logical path of a hashed file name
+    pub fn resolve(&self, hashed: &str) -> Option<&str> {
+        self.logical.get(hashed).map(String::as_str)
+    }
+
+    /// `(logical, hashed)` pairs
+    pub fn files(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.hashed.iter().map(|(l, h)| (l.as_str(), h.as_str()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.hashed.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hashed.is_empty()
+    }
+}
+
+/// `css/style.css` → `css/style.<hash>.css`
+fn hashed_name(logical: &str, hash: &str) -> String {
+    let (dir, file) = logical.rsplit_once('/').unwrap_or(("", logical));
+    let file = match file.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => format!("{}.{}.{}", stem, hash, ext),
+        _ => format!("{}.{}", file, hash),
+    };
+    if dir.is_empty() {
+        file
+    } else {
+        format!("{}/{}", dir, file)
+    }
+}
+
+/// Install the manifest used by [`url`]; only the first call has an effect
+pub fn init(manifest: AssetManifest) {
+    if MANIFEST.set(manifest).is_err() {
+        tracing::debug!("Asset manifest already initialized");
+    }
+}
+
+/// The installed manifest (empty before [`init`])
+pub fn manifest() -> &'static AssetManifest {
+    MANIFEST.get_or_init(AssetManifest::default)
+}
+
+/// Fingerprinted URL of a file under `static/`, for templates:
+/// `{{ crate::assets::url("css/style.css") }}`
+pub fn url(path: &str) -> String {
+    manifest().url(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hashed_urls() {
+        let mut manifest = AssetManifest::default();
+        manifest.insert("css/style.css".into(), "0123abcd");
+        manifest.insert("favicon.ico".into(), "89ef4567");
+        manifest.insert(".well-known".into(), "aaaa0000");
+
+        assert_eq!(
+            manifest.url("css/style.css"),
+            "/static/css/style.0123abcd.css"
+        );
+        assert_eq!(manifest.url("/favicon.ico"), "/static/favicon.89ef4567.ico");
+        assert_eq!(manifest.url("js/missing.js"), "/static/js/missing.js");
+        assert_eq!(
+            manifest.resolve("css/style.0123abcd.css"),
+            Some("css/style.css")
+        );
+        assert_eq!(
+            manifest.resolve(".well-known.aaaa0000"),
+            Some(".well-known")
+        );
+        assert_eq!(manifest.resolve("css/style.css"), None);
+    }
+}
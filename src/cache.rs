@@ -0,0 +1,181 @@
+//! In-memory cache of rendered pages.
+//!
+//! Content only changes on reload, so rendered responses are kept until
+//! [`PageCache::clear`] is called from [`crate::state::AppState::reload_content`]
+//! or their TTL runs out. The TTL bounds how stale per-request data on list
+//! pages (view counts) can get.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+use axum::body::Bytes;
+use axum::http::{Extensions, HeaderMap};
+use serde::Serialize;
+
+/// Upper bound on cached responses; new pages are not cached beyond it
+const MAX_ENTRIES: usize = 2000;
+
+/// A rendered 200 response
+#[derive(Debug, Clone)]
+pub struct CachedResponse {
+    pub headers: HeaderMap,
+    pub extensions: Extensions,
+    pub body: Bytes,
+    stored_at: Instant,
+}
+
+/// Hit and miss counters, reported by `/health`
+#[derive(Debug, Serialize)]
+pub struct CacheStats {
+    pub entries: usize,
+    pub hits: u64,
+    pub misses: u64,
+}
+
+#[derive(Debug)]
+pub struct PageCache {
+    ttl: Duration,
+    entries: RwLock<HashMap<String, CachedResponse>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    /// Bumped on every clear, so pages rendered from old content are not
+    /// stored after a reload
+    generation: AtomicU64,
+}
+
+impl PageCache {
+    /// A zero TTL disables caching
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: RwLock::new(HashMap::new()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            generation: AtomicU64::new(0),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.ttl.is_zero()
+    }
+
+    /// Fresh response for `key`, counting the lookup as a hit or miss
+    pub fn get(&self, key: &str) -> Option<CachedResponse> {
+        let entries = self.entries.read().unwrap_or_else(|e| e.into_inner());
+        let cached = entries
+            .get(key)
+            .filter(|c| c.stored_at.elapsed() < self.ttl)
+            .cloned();
+
+        let counter = if cached.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        cached
+    }
+
+    /// Current generation, taken before rendering a page to insert
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Acquire)
+    }
+
+    /// Store a response rendered during `generation`; dropped if the cache
+    /// was cleared in the meantime
+    pub fn insert(
+        &self,
+        generation: u64,
+        key: String,
+        headers: HeaderMap,
+        extensions: Extensions,
+        body: Bytes,
+    ) {
+        let mut entries = self.entries.write().unwrap_or_else(|e| e.into_inner());
+        if generation != self.generation() {
+            return;
+        }
+        if entries.len() >= MAX_ENTRIES {
+            entries.retain(|_, c| c.stored_at.elapsed() < self.ttl);
+            if entries.len() >= MAX_ENTRIES {
+                return;
+            }
+        }
+
+        entries.insert(
+            key,
+            CachedResponse {
+                headers,
+                extensions,
+                body,
+                stored_at: Instant::now(),
+            },
+        );
+    }
+
+    /// Drop every cached response
+    pub fn clear(&self) {
+        let mut entries = self.entries.write().unwrap_or_else(|e| e.into_inner());
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        entries.clear();
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            entries: self.entries.read().unwrap_or_else(|e| e.into_inner()).len(),
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_hits_and_clear() {
+        let cache = PageCache::new(Duration::from_secs(60));
+        let store = |key: &str| {
+            cache.insert(
+                cache.generation(),
+                key.to_string(),
+                HeaderMap::new(),
+                Extensions::new(),
+                Bytes::from_static(b"page"),
+            )
+        };
+
+        assert!(cache.get("/blog en").is_none());
+        store("/blog en");
+        assert_eq!(cache.get("/blog en").unwrap().body, "page");
+        assert!(cache.get("/blog ru").is_none());
+
+        let before_clear = cache.generation();
+        cache.clear();
+        assert!(cache.get("/blog en").is_none());
+        cache.insert(
+            before_clear,
+            "/blog en".into(),
+            HeaderMap::new(),
+            Extensions::new(),
+            Bytes::new(),
+        );
+
+        let stats = cache.stats();
+        assert_eq!((stats.entries, stats.hits, stats.misses), (0, 1, 3));
+
+        let expired = PageCache::new(Duration::from_nanos(1));
+        expired.insert(
+            expired.generation(),
+            "/".into(),
+            HeaderMap::new(),
+            Extensions::new(),
+            Bytes::new(),
+        );
+        std::thread::sleep(Duration::from_millis(1));
+        assert!(expired.get("/").is_none());
+    }
+}
@@ -13,6 +13,8 @@ pub struct Config {
     pub site_description: String,
     pub author_name: String,
     pub author_email: String,
+    // Slug of the author profile used for posts without `authors`
+    pub default_author: Option<String>,
     // SMTP configuration for contact form
     pub smtp_host: Option<String>,
     pub smtp_port: u16,
@@ -30,6 +32,22 @@ pub struct Config {
     pub redis_url: Option<String>,
     // Cloudinary configuration
     pub cloudinary_base_url: String,
+    // Number of items per page on list pages
+    pub page_size: usize,
+    // Seconds a rendered page stays cached; 0 disables the page cache
+    pub page_cache_ttl: u64,
+    // Number of items in RSS, Atom and JSON feeds
+    pub feed_items: usize,
+    // Directory for generated Open Graph images
+    pub og_cache_dir: String,
+    // Accept Webmentions and show approved ones under posts
+    pub webmentions: bool,
+    // Paths disallowed for every crawler in robots.txt
+    pub robots_disallow: Vec<String>,
+    // User agents (e.g. AI crawlers) disallowed from the whole site
+    pub robots_blocked_agents: Vec<String>,
+    // Content languages; the first one is the default and has unprefixed URLs
+    pub languages: Vec<String>,
     // Environment (development/production)
     pub environment: String,
 }
@@ -43,7 +61,8 @@ impl Config {
                 .unwrap_or_else(|_| "3000".into())
                 .parse()
                 .context("Invalid PORT")?,
-            database_url: env::var("DATABASE_URL").context("DATABASE_URL must be set")?,
+            // Only the server needs a database; `nebula build` runs without one
+            database_url: env::var("DATABASE_URL").unwrap_or_default(),
             content_dir: env::var("CONTENT_DIR").unwrap_or_else(|_| "./content".into()),
             site_url: env::var("SITE_URL").unwrap_or_else(|_| "http://localhost:3000".into()),
             site_title: env::var("SITE_TITLE").unwrap_or_else(|_| "Nebula".into()),
@@ -51,6 +70,7 @@ impl Config {
                 .unwrap_or_else(|_| "Personal blog and project showcase".into()),
             author_name: env::var("AUTHOR_NAME").unwrap_or_else(|_| "Author".into()),
             author_email: env::var("AUTHOR_EMAIL").unwrap_or_else(|_| "author@example.com".into()),
+            default_author: env::var("DEFAULT_AUTHOR").ok(),
             smtp_host: env::var("SMTP_HOST").ok(),
             smtp_port: env::var("SMTP_PORT")
                 .unwrap_or_else(|_| "587".into())
@@ -69,6 +89,32 @@ impl Config {
             cloudinary_base_url: env::var("CLOUDINARY_BASE_URL").unwrap_or_else(|_| {
                 "https://res.cloudinary.com/ddkzhz9b4/image/upload/nebula".into()
             }),
+            page_size: env::var("PAGE_SIZE")
+                .unwrap_or_else(|_| "10".into())
+                .parse()
+                .context("Invalid PAGE_SIZE")?,
+            page_cache_ttl: env::var("PAGE_CACHE_TTL")
+                .unwrap_or_else(|_| "60".into())
+                .parse()
+                .context("Invalid PAGE_CACHE_TTL")?,
+            feed_items: env::var("FEED_ITEMS")
+                .unwrap_or_else(|_| "20".into())
+                .parse()
+                .context("Invalid FEED_ITEMS")?,
+            og_cache_dir: env::var("OG_CACHE_DIR").unwrap_or_else(|_| {
+                env::temp_dir()
+                    .join("nebula-og")
+                    .to_string_lossy()
+                    .into_owned()
+            }),
+            webmentions: !matches!(env::var("WEBMENTIONS").as_deref(), Ok("false" | "0")),
+            robots_disallow: parse_list(
+                &env::var("ROBOTS_DISALLOW").unwrap_or_else(|_| "/admin,/health".into()),
+            ),
+            robots_blocked_agents: parse_list(
+                &env::var("ROBOTS_BLOCKED_AGENTS").unwrap_or_default(),
+            ),
+            languages: parse_languages(&env::var("LANGUAGES").unwrap_or_else(|_| "en".into())),
             environment: env::var("ENVIRONMENT").unwrap_or_else(|_| "development".into()),
         })
     }
@@ -93,6 +139,11 @@ impl Config {
         self.redis_url.is_some()
     }
 
+    /// Language served without a URL prefix
+    pub fn default_language(&self) -> &str {
+        &self.languages[0]
+    }
+
     /// Check if running in production
     pub fn is_production(&self) -> bool {
         self.environment == "production"
@@ -104,6 +155,7 @@ impl Config {
     ///
     /// Accepts either:
     /// - Full URL (returned as-is)
+    /// - Site-relative path, e.g. a page bundle asset (served by the site itself)
     /// - Filename only (resolved based on environment)
     pub fn resolve_cover_url(&self, cover_image: &str) -> String {
         // Already a full URL - return as-is
@@ -111,11 +163,54 @@ impl Config {
             return cover_image.to_string();
         }
 
+        // Served by the site, not Cloudinary; absolute in production so it
+        // works as an og:image
+        if cover_image.starts_with('/') {
+            return if self.is_production() {
+                format!("{}{}", self.site_url, cover_image)
+            } else {
+                cover_image.to_string()
+            };
+        }
+
         // Filename only - resolve based on environment
         if self.is_production() {
             format!("{}/{}", self.cloudinary_base_url, cover_image)
         } else {
-            format!("/static/images/{}", cover_image)
+            crate::assets::url(&format!("images/{}", cover_image))
+        }
+    }
+
+    /// Absolute URL for a site-relative one; full URLs are returned as-is
+    pub fn absolute_url(&self, url: &str) -> String {
+        if url.starts_with('/') {
+            format!("{}{}", self.site_url, url)
+        } else {
+            url.to_string()
         }
     }
 }
+
+/// Parse a comma-separated list, skipping empty items
+fn parse_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+/// Parse a comma-separated language list, falling back to English
+fn parse_languages(value: &str) -> Vec<String> {
+    let languages: Vec<String> = value
+        .split(',')
+        .map(|l| l.trim().to_lowercase())
+        .filter(|l| !l.is_empty())
+        .collect();
+
+    if languages.is_empty() {
+        vec!["en".into()]
+    } else {
+        languages
+    }
+}
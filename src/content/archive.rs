@@ -0,0 +1,116 @@
+//! Date-based grouping of published posts for archive pages.
+
+use chrono::Datelike;
+
+use crate::models::Post;
+
+/// Posts published in one year, grouped by month (newest first)
+#[derive(Debug)]
+pub struct ArchiveYear<'a> {
+    pub year: i32,
+    pub count: usize,
+    pub months: Vec<ArchiveMonth<'a>>,
+}
+
+/// Posts published in one month (newest first)
+#[derive(Debug)]
+pub struct ArchiveMonth<'a> {
+    pub year: i32,
+    pub month: u32,
+    pub posts: Vec<&'a Post>,
+}
+
+impl ArchiveMonth<'_> {
+    /// Message id of the month name, e.g. `month-1`
+    pub fn name_id(&self) -> String {
+        month_id(self.month)
+    }
+
+    pub fn count(&self) -> usize {
+        self.posts.len()
+    }
+}
+
+/// Group posts sorted newest first into years and months
+pub fn group<'a>(posts: &[&'a Post]) -> Vec<ArchiveYear<'a>> {
+    let mut years: Vec<ArchiveYear<'a>> = Vec::new();
+
+    for &post in posts {
+        let date = post.metadata.date;
+        let (year, month) = (date.year(), date.month());
+
+        if years.last().map(|y| y.year) != Some(year) {
+            years.push(ArchiveYear {
+                year,
+                count: 0,
+                months: Vec::new(),
+            });
+        }
+        let archive_year = years.last_mut().expect("year was just pushed");
+        archive_year.count += 1;
+
+        if archive_year.months.last().map(|m| m.month) != Some(month) {
+            archive_year.months.push(ArchiveMonth {
+                year,
+                month,
+                posts: Vec::new(),
+            });
+        }
+        archive_year
+            .months
+            .last_mut()
+            .expect("month was just pushed")
+            .posts
+            .push(post);
+    }
+
+    years
+}
+
+/// Message id of the name of a 1-based month number
+pub fn month_id(month: u32) -> String {
+    format!("month-{}", month)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, Utc};
+
+    fn post(slug: &str, date: &str) -> Post {
+        let mut post = Post::fixture(slug);
+        post.metadata.date = DateTime::parse_from_rfc3339(date)
+            .unwrap()
+            .with_timezone(&Utc);
+        post
+    }
+
+    #[test]
+    fn test_group_by_year_and_month() {
+        let posts = [
+            post("march-late", "2025-03-20T10:00:00Z"),
+            post("march-early", "2025-03-02T10:00:00Z"),
+            post("january", "2025-01-15T10:00:00Z"),
+            post("december", "2024-12-31T23:00:00Z"),
+        ];
+        let refs: Vec<&Post> = posts.iter().collect();
+
+        let years = group(&refs);
+        fn slugs<'a>(month: &ArchiveMonth<'a>) -> Vec<&'a str> {
+            month
+                .posts
+                .iter()
+                .map(|p| p.metadata.slug.as_str())
+                .collect()
+        }
+        assert_eq!(
+            years.iter().map(|y| (y.year, y.count)).collect::<Vec<_>>(),
+            [(2025, 3), (2024, 1)]
+        );
+        let months: Vec<_> = years[0].months.iter().map(|m| m.month).collect();
+        assert_eq!(months, [3, 1]);
+        assert_eq!(slugs(&years[0].months[0]), ["march-late", "march-early"]);
+        assert_eq!(slugs(&years[1].months[0]), ["december"]);
+        assert_eq!(years[0].months[0].name_id(), "month-3");
+    }
+}
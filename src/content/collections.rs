@@ -0,0 +1,251 @@
+//! Content collections declared in `content/collections.yaml`.
+//!
+//! Blog posts and projects have dedicated loaders and routes; any other
+//! section (talks, notes, TIL, ...) is a collection: a directory of markdown
+//! entries served under a URL prefix with the generic collection templates.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use tokio::fs;
+
+use crate::models::Entry;
+
+/// Settings of one collection. Every field has a default derived from the
+/// collection name.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct CollectionConfig {
+    /// Page title of the list page (defaults to the capitalized name)
+    pub title: Option<String>,
+    pub description: Option<String>,
+    /// Directory under the content dir (defaults to the name)
+    pub dir: Option<String>,
+    /// URL prefix (defaults to `/<name>`)
+    pub path: Option<String>,
+    pub templates: CollectionTemplates,
+    pub sort: SortOrder,
+    /// Include entries in the RSS, Atom and JSON feeds
+    pub feed: bool,
+    /// Include the list page and entries in the sitemap
+    pub sitemap: bool,
+}
+
+/// Which of the compiled collection layouts to use
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(default)]
+pub struct CollectionTemplates {
+    pub list: ListLayout,
+    pub item: ItemLayout,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ListLayout {
+    /// One entry per row, like the blog
+    #[default]
+    List,
+    /// Cards, like the projects page
+    Grid,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ItemLayout {
+    /// Dated article with tags and `extra` fields
+    #[default]
+    Article,
+    /// Plain page without metadata
+    Page,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    /// Newest first
+    #[default]
+    DateDesc,
+    /// Oldest first
+    DateAsc,
+    Title,
+    /// By the `weight` field, lowest first
+    Weight,
+}
+
+/// A loaded collection
+#[derive(Debug)]
+pub struct Collection {
+    pub name: String,
+    pub title: String,
+    pub description: Option<String>,
+    /// URL prefix without a trailing slash
+    pub path: String,
+    pub templates: CollectionTemplates,
+    pub sort: SortOrder,
+    pub feed: bool,
+    pub sitemap: bool,
+    pub entries: HashMap<String, Entry>,
+}
+
+impl Collection {
+    fn new(name: &str, config: CollectionConfig) -> Self {
+        let path = config.path.unwrap_or_else(|| format!("/{}", name));
+        let path = format!("/{}", path.trim_matches('/'));
+
+        Self {
+            name: name.to_string(),
+            title: config.title.unwrap_or_else(|| capitalize(name)),
+            description: config.description,
+            path,
+            templates: config.templates,
+            sort: config.sort,
+            feed: config.feed,
+            sitemap: config.sitemap,
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Published entries in the configured order
+    pub fn published_entries(&self) -> Vec<&Entry> {
+        let mut entries: Vec<_> = self
+            .entries
+            .values()
+            .filter(|e| !e.metadata.draft)
+            .collect();
+
+        match self.sort {
+            SortOrder::DateDesc => entries.sort_by_key(|e| std::cmp::Reverse(e.metadata.date)),
+            SortOrder::DateAsc => entries.sort_by_key(|e| e.metadata.date),
+            SortOrder::Title => entries.sort_by_key(|e| e.metadata.title.to_lowercase()),
+            // Entries without a weight go last
+            SortOrder::Weight => {
+                entries.sort_by_key(|e| (e.metadata.weight.is_none(), e.metadata.weight))
+            }
+        }
+        entries
+    }
+
+    /// Site-relative URL of an entry
+    pub fn entry_path(&self, slug: &str) -> String {
+        format!("{}/{}", self.path, slug)
+    }
+}
+
+/// Read the collection settings file; a missing file means no collections
+pub async fn load_config(path: &Path) -> Result<BTreeMap<String, CollectionConfig>> {
+    if !path.exists() {
+        return Ok(BTreeMap::new());
+    }
+
+    let content = fs::read_to_string(path).await?;
+    let config: Option<BTreeMap<String, Option<CollectionConfig>>> =
+        serde_yaml::from_str(&content).with_context(|| format!("Failed to parse {:?}", path))?;
+
+    // A bare `talks:` line declares a collection with all defaults
+    Ok(config
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(name, config)| (name, config.unwrap_or_default()))
+        .collect())
+}
+
+/// Load every configured collection from the content directory. Collections
+/// whose URL clashes with a built-in route or another collection are skipped.
+pub async fn load(content_dir: &Path, languages: &[String]) -> Result<Vec<Collection>> {
+    let configs = load_config(&content_dir.join("collections.yaml")).await?;
+    let mut collections: Vec<Collection> = Vec::new();
+
+    for (name, config) in configs {
+        let dir = content_dir.join(config.dir.as_deref().unwrap_or(&name));
+        let mut collection = Collection::new(&name, config);
+
+        if super::is_reserved_path(&collection.path, languages) {
+            tracing::warn!(
+                "Collection {:?} uses reserved path {:?}, skipping",
+                name,
+                collection.path
+            );
+            continue;
+        }
+        if collections.iter().any(|c| c.path == collection.path) {
+            tracing::warn!(
+                "Collection {:?} uses path {:?} of another collection, skipping",
+                name,
+                collection.path
+            );
+            continue;
+        }
+
+        if dir.exists() {
+            collection.entries = super::markdown::load_entries(&dir).await?;
+        } else {
+            tracing::warn!(
+                "Directory {:?} of collection {:?} does not exist",
+                dir,
+                name
+            );
+        }
+        collections.push(collection);
+    }
+
+    Ok(collections)
+}
+
+fn capitalize(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::EntryMetadata;
+
+    fn entry(slug: &str, weight: Option<i64>) -> Entry {
+        Entry {
+            metadata: EntryMetadata {
+                title: slug.to_string(),
+                slug: slug.to_string(),
+                description: None,
+                date: None,
+                updated: None,
+                draft: false,
+                weight,
+                tags: Vec::new(),
+                cover_image: None,
+                extra: BTreeMap::new(),
+            },
+            content_raw: String::new(),
+            content_html: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_config_defaults_and_sorting() {
+        let configs: BTreeMap<String, CollectionConfig> = serde_yaml::from_str(
+            "til:\n  path: /notes/til/\n  sort: weight\n  templates:\n    list: grid\n",
+        )
+        .unwrap();
+        let mut til = Collection::new("til", configs["til"].clone());
+        assert_eq!(til.title, "Til");
+        assert_eq!(til.path, "/notes/til");
+        assert_eq!(til.templates.list, ListLayout::Grid);
+        assert_eq!(til.templates.item, ItemLayout::Article);
+
+        for (slug, weight) in [("c", None), ("b", Some(2)), ("a", Some(1))] {
+            til.entries.insert(slug.to_string(), entry(slug, weight));
+        }
+        let order: Vec<_> = til
+            .published_entries()
+            .iter()
+            .map(|e| e.metadata.slug.as_str())
+            .collect();
+        assert_eq!(order, ["a", "b", "c"]);
+        assert_eq!(til.entry_path("a"), "/notes/til/a");
+    }
+}
@@ -0,0 +1,134 @@
+//! Edit history of content files from git.
+//!
+//! When the content directory is inside a git repository, a single
+//! `git log` over it gives every file's revisions. Without git (or outside a
+//! repository, e.g. in the Docker image) history is simply unavailable.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use tokio::process::Command;
+
+use crate::models::Revision;
+
+/// Field and record separators in the `git log` format
+const FIELD_SEP: char = '\x1f';
+const RECORD_SEP: char = '\x1e';
+
+/// Revisions per file, newest first, keyed by path relative to the content dir
+#[derive(Debug, Default)]
+pub struct GitHistory {
+    files: HashMap<PathBuf, Vec<Revision>>,
+}
+
+impl GitHistory {
+    /// Read the history of `content_dir`; `None` if git is unavailable or the
+    /// directory is not in a repository
+    pub async fn load(content_dir: &Path) -> Option<Self> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(content_dir)
+            .args([
+                "-c",
+                "core.quotePath=false",
+                "log",
+                "--relative",
+                "--name-only",
+                "--no-merges",
+            ])
+            .arg(format!(
+                "--format={}%H{}%aI{}%an{}%s",
+                RECORD_SEP, FIELD_SEP, FIELD_SEP, FIELD_SEP
+            ))
+            .args(["--", "."])
+            .output()
+            .await;
+
+        match output {
+            Ok(output) if output.status.success() => {
+                Some(Self::parse(&String::from_utf8_lossy(&output.stdout)))
+            }
+            Ok(output) => {
+                tracing::info!(
+                    "No git history for {:?}: {}",
+                    content_dir,
+                    String::from_utf8_lossy(&output.stderr).trim()
+                );
+                None
+            }
+            Err(e) => {
+                tracing::info!("No git history for {:?}: {}", content_dir, e);
+                None
+            }
+        }
+    }
+
+    fn parse(log: &str) -> Self {
+        let mut files: HashMap<PathBuf, Vec<Revision>> = HashMap::new();
+
+        for record in log.split(RECORD_SEP).filter(|r| !r.trim().is_empty()) {
+            let mut lines = record.lines();
+            let Some(header) = lines.next() else {
+                continue;
+            };
+            let fields: Vec<_> = header.splitn(4, FIELD_SEP).collect();
+            let [hash, date, author, subject] = fields[..] else {
+                continue;
+            };
+            let Ok(date) = DateTime::parse_from_rfc3339(date) else {
+                continue;
+            };
+
+            let revision = Revision {
+                hash: hash.to_string(),
+                date: date.with_timezone(&Utc),
+                author: author.to_string(),
+                subject: subject.to_string(),
+            };
+            for file in lines.map(str::trim).filter(|l| !l.is_empty()) {
+                files
+                    .entry(PathBuf::from(file))
+                    .or_default()
+                    .push(revision.clone());
+            }
+        }
+
+        Self { files }
+    }
+
+    /// Revisions of a file (newest first); `path` may include the content dir
+    pub fn revisions(&self, content_dir: &Path, path: &Path) -> Vec<Revision> {
+        let relative = path.strip_prefix(content_dir).unwrap_or(path);
+        self.files.get(relative).cloned().unwrap_or_default()
+    }
+}
+
+/// Last update time from history: the newest commit, if the file changed
+/// after the commit that added it
+pub fn last_updated(revisions: &[Revision]) -> Option<DateTime<Utc>> {
+    (revisions.len() > 1).then(|| revisions[0].date)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_log() {
+        let log = "\x1eb2\x1f2025-03-02T10:00:00+03:00\x1fAlice\x1fFix typo\n\nblog/a.md\n\
+                   \x1ea1\x1f2025-03-01T10:00:00+00:00\x1fBob\x1fAdd posts\n\nblog/a.md\nblog/b.md\n";
+        let history = GitHistory::parse(log);
+        let content_dir = Path::new("./content");
+
+        let a = history.revisions(content_dir, Path::new("./content/blog/a.md"));
+        assert_eq!(a.len(), 2);
+        assert_eq!(a[0].subject, "Fix typo");
+        assert_eq!(a[0].date.to_rfc3339(), "2025-03-02T07:00:00+00:00");
+        assert_eq!(last_updated(&a), Some(a[0].date));
+
+        let b = history.revisions(content_dir, Path::new("./content/blog/b.md"));
+        assert_eq!(b.len(), 1);
+        assert_eq!(last_updated(&b), None);
+    }
+}
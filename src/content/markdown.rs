@@ -1,5 +1,5 @@
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 use pulldown_cmark::{html, Options, Parser};
@@ -8,7 +8,11 @@ use syntect::html::highlighted_html_for_string;
 use syntect::parsing::SyntaxSet;
 use tokio::fs;
 
-use crate::models::{Post, PostMetadata, Project, ProjectMetadata};
+use crate::i18n::{self, Lang};
+use crate::models::{
+    Author, AuthorMetadata, Entry, EntryMetadata, Page, PageMetadata, Post, PostMetadata, Project,
+    ProjectMetadata,
+};
 
 /// Parse frontmatter and content from a markdown file
 fn parse_frontmatter<T: serde::de::DeserializeOwned>(content: &str) -> Result<(T, String)> {
@@ -34,13 +38,17 @@ fn parse_frontmatter<T: serde::de::DeserializeOwned>(content: &str) -> Result<(T
     Ok((metadata, body.to_string()))
 }
 
-/// Simple frontmatter parser for YAML-like format
+/// Simple frontmatter parser for YAML-like format.
+///
+/// A key with no value followed by indented `key: value` lines becomes a
+/// nested map (used for `extra`).
 fn parse_simple_frontmatter<T: serde::de::DeserializeOwned>(content: &str) -> Result<T> {
     // Convert simple YAML to JSON for parsing
     let mut json_obj = serde_json::Map::new();
+    let mut nested: Option<String> = None;
 
-    for line in content.lines() {
-        let line = line.trim();
+    for raw_line in content.lines() {
+        let line = raw_line.trim();
         if line.is_empty() || line.starts_with('#') {
             continue;
         }
@@ -49,32 +57,95 @@ fn parse_simple_frontmatter<T: serde::de::DeserializeOwned>(content: &str) -> Re
             let key = key.trim();
             let value = value.trim().trim_matches('"').trim_matches('\'');
 
-            // Handle arrays (simple case: comma-separated)
-            if value.starts_with('[') && value.ends_with(']') {
-                let items: Vec<serde_json::Value> = value[1..value.len() - 1]
-                    .split(',')
-                    .map(|s| serde_json::Value::String(s.trim().trim_matches('"').to_string()))
-                    .collect();
-                json_obj.insert(key.to_string(), serde_json::Value::Array(items));
-            } else if value == "true" {
-                json_obj.insert(key.to_string(), serde_json::Value::Bool(true));
-            } else if value == "false" {
-                json_obj.insert(key.to_string(), serde_json::Value::Bool(false));
-            } else {
+            let indented = raw_line.starts_with([' ', '\t']);
+            if let Some(serde_json::Value::Object(map)) = nested
+                .as_ref()
+                .filter(|_| indented)
+                .and_then(|parent| json_obj.get_mut(parent))
+            {
+                map.insert(key.to_string(), simple_value(value));
+                continue;
+            }
+
+            if value.is_empty() {
+                nested = Some(key.to_string());
                 json_obj.insert(
                     key.to_string(),
-                    serde_json::Value::String(value.to_string()),
+                    serde_json::Value::Object(serde_json::Map::new()),
                 );
+            } else {
+                nested = None;
+                json_obj.insert(key.to_string(), simple_value(value));
             }
         }
     }
 
+    // Keys without a value or nested lines stay empty strings
+    for value in json_obj.values_mut() {
+        if value.as_object().is_some_and(|map| map.is_empty()) {
+            *value = serde_json::Value::String(String::new());
+        }
+    }
+
     serde_json::from_value(serde_json::Value::Object(json_obj))
         .context("Failed to parse frontmatter as JSON")
 }
 
+/// Convert a simple frontmatter value to JSON
+fn simple_value(value: &str) -> serde_json::Value {
+    // Handle arrays (simple case: comma-separated)
+    if value.starts_with('[') && value.ends_with(']') {
+        let items: Vec<serde_json::Value> = value[1..value.len() - 1]
+            .split(',')
+            .map(|s| serde_json::Value::String(s.trim().trim_matches('"').to_string()))
+            .collect();
+        serde_json::Value::Array(items)
+    } else if value == "true" {
+        serde_json::Value::Bool(true)
+    } else if value == "false" {
+        serde_json::Value::Bool(false)
+    } else {
+        serde_json::Value::String(value.to_string())
+    }
+}
+
 /// Convert markdown to HTML with syntax highlighting
 pub fn render_markdown(content: &str) -> String {
+    render(content, None)
+}
+
+/// Same as [`render_markdown`] for a page bundle: relative image sources and
+/// links to files are rewritten to URLs under `asset_base`
+pub fn render_bundle_markdown(content: &str, asset_base: &str) -> String {
+    render(content, Some(asset_base))
+}
+
+/// URL of a bundle asset for a relative link, `None` for anything that is not
+/// a file next to the markdown (absolute paths, other sites, anchors, `../`).
+/// Links without a file extension are left alone so relative links between
+/// posts keep working; images are always assets.
+fn bundle_asset_url(url: &str, asset_base: &str, is_image: bool) -> Option<String> {
+    let has_scheme = url
+        .split_once(':')
+        .is_some_and(|(scheme, _)| !scheme.contains('/'));
+    if url.is_empty() || has_scheme || url.starts_with(['/', '#', '?']) {
+        return None;
+    }
+
+    let relative = url.trim_start_matches("./");
+    if relative.starts_with("../") {
+        return None;
+    }
+    let file = relative.split(['?', '#']).next().unwrap_or_default();
+    let looks_like_file = file.rsplit('/').next().is_some_and(|f| f.contains('.'));
+    if !is_image && !looks_like_file {
+        return None;
+    }
+
+    Some(format!("{}/{}", asset_base.trim_end_matches('/'), relative))
+}
+
+fn render(content: &str, asset_base: Option<&str>) -> String {
     let ss = SyntaxSet::load_defaults_newlines();
     let ts = ThemeSet::load_defaults();
     let theme = &ts.themes["base16-ocean.dark"];
@@ -131,6 +202,38 @@ pub fn render_markdown(content: &str) -> String {
             pulldown_cmark::Event::Text(text) if in_code_block => {
                 code_content.push_str(&text);
             }
+            pulldown_cmark::Event::Start(pulldown_cmark::Tag::Image {
+                link_type,
+                dest_url,
+                title,
+                id,
+            }) => {
+                let dest_url = asset_base
+                    .and_then(|base| bundle_asset_url(&dest_url, base, true))
+                    .map_or(dest_url, Into::into);
+                events.push(pulldown_cmark::Event::Start(pulldown_cmark::Tag::Image {
+                    link_type,
+                    dest_url,
+                    title,
+                    id,
+                }));
+            }
+            pulldown_cmark::Event::Start(pulldown_cmark::Tag::Link {
+                link_type,
+                dest_url,
+                title,
+                id,
+            }) => {
+                let dest_url = asset_base
+                    .and_then(|base| bundle_asset_url(&dest_url, base, false))
+                    .map_or(dest_url, Into::into);
+                events.push(pulldown_cmark::Event::Start(pulldown_cmark::Tag::Link {
+                    link_type,
+                    dest_url,
+                    title,
+                    id,
+                }));
+            }
             _ => events.push(event),
         }
     }
@@ -140,10 +243,136 @@ pub fn render_markdown(content: &str) -> String {
     html_output
 }
 
-/// Load all posts from a directory
-pub async fn load_posts(dir: &Path) -> Result<HashMap<String, Post>> {
+/// Load all posts from a directory, keyed by [`super::post_key`].
+///
+/// A post is either `slug.md` or a page bundle `slug/index.md` with its
+/// assets next to it. `slug.<lang>.md` (or `slug/index.<lang>.md`) files are
+/// translations; posts in other languages are served under the language's
+/// URL prefix. The first of `languages` is the default one; posts in a
+/// language that isn't configured are skipped with a warning.
+pub async fn load_posts(dir: &Path, languages: &[String]) -> Result<HashMap<String, Post>> {
+    let default_language = languages[0].as_str();
     let mut posts = HashMap::new();
 
+    for (path, bundle) in post_files(dir, languages).await? {
+        let content = fs::read_to_string(&path).await?;
+
+        match parse_frontmatter::<PostMetadata>(&content) {
+            Ok((mut metadata, body)) => {
+                let reading_time = Post::estimate_reading_time(&body);
+
+                let stem = path
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or_default();
+                let (file_name, file_lang) = i18n::split_lang_suffix(stem, languages);
+                let base_name = bundle
+                    .as_deref()
+                    .and_then(|b| b.file_name())
+                    .and_then(|n| n.to_str())
+                    .unwrap_or(file_name);
+                let lang = metadata
+                    .lang
+                    .as_deref()
+                    .or(file_lang)
+                    .unwrap_or(default_language)
+                    .to_string();
+                if !languages.contains(&lang) {
+                    tracing::warn!(
+                        "Post {:?} is in {:?}, which is not in LANGUAGES, skipping",
+                        path,
+                        lang
+                    );
+                    continue;
+                }
+                let translation_key = metadata
+                    .translation_key
+                    .clone()
+                    .unwrap_or_else(|| base_name.to_string());
+                let url =
+                    Lang::new(&lang, default_language).path(&format!("/blog/{}", metadata.slug));
+
+                let html = match &bundle {
+                    Some(bundle) => {
+                        let asset_base = format!("{}/assets", url);
+                        // A cover image inside the bundle is served like any
+                        // other asset
+                        if let Some(cover) = metadata.cover_image.as_mut() {
+                            if let Some(asset) = bundle_asset_url(cover, &asset_base, true)
+                                .filter(|_| bundle.join(cover.as_str()).is_file())
+                            {
+                                *cover = asset;
+                            }
+                        }
+                        render_bundle_markdown(&body, &asset_base)
+                    }
+                    None => render_markdown(&body),
+                };
+
+                let key = super::post_key(&lang, &metadata.slug, default_language);
+                let updated = metadata.updated;
+                let post = Post {
+                    metadata,
+                    content_raw: body,
+                    content_html: html,
+                    reading_time_minutes: reading_time,
+                    lang,
+                    translation_key,
+                    path: url,
+                    source: path.clone(),
+                    bundle,
+                    updated,
+                    revisions: Vec::new(),
+                };
+
+                if posts.insert(key, post).is_some() {
+                    tracing::warn!("Duplicate post slug in {:?}", path);
+                }
+            }
+            Err(e) => {
+                tracing::warn!("Failed to parse post {:?}: {}", path, e);
+            }
+        }
+    }
+
+    Ok(posts)
+}
+
+/// Markdown files of posts in `dir` with the bundle directory of each, if any
+async fn post_files(dir: &Path, languages: &[String]) -> Result<Vec<(PathBuf, Option<PathBuf>)>> {
+    let mut files = Vec::new();
+
+    let mut entries = fs::read_dir(dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+
+        if entry.file_type().await?.is_dir() {
+            let mut bundle_entries = fs::read_dir(&path).await?;
+            while let Some(bundle_entry) = bundle_entries.next_entry().await? {
+                let file = bundle_entry.path();
+                let stem = file
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or_default();
+                if file.extension().is_some_and(|e| e == "md")
+                    && i18n::split_lang_suffix(stem, languages).0 == "index"
+                {
+                    files.push((file, Some(path.clone())));
+                }
+            }
+        } else if path.extension().is_some_and(|e| e == "md") {
+            files.push((path, None));
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+/// Load all projects from a directory
+pub async fn load_projects(dir: &Path) -> Result<HashMap<String, Project>> {
+    let mut projects = HashMap::new();
+
     let mut entries = fs::read_dir(dir).await?;
     while let Some(entry) = entries.next_entry().await? {
         let path = entry.path();
@@ -151,33 +380,33 @@ pub async fn load_posts(dir: &Path) -> Result<HashMap<String, Post>> {
         if path.extension().is_some_and(|e| e == "md") {
             let content = fs::read_to_string(&path).await?;
 
-            match parse_frontmatter::<PostMetadata>(&content) {
+            match parse_frontmatter::<ProjectMetadata>(&content) {
                 Ok((metadata, body)) => {
                     let html = render_markdown(&body);
-                    let reading_time = Post::estimate_reading_time(&body);
 
-                    let post = Post {
+                    let project = Project {
                         metadata: metadata.clone(),
                         content_raw: body,
                         content_html: html,
-                        reading_time_minutes: reading_time,
+                        source: path.clone(),
+                        updated: metadata.updated,
                     };
 
-                    posts.insert(metadata.slug.clone(), post);
+                    projects.insert(metadata.slug.clone(), project);
                 }
                 Err(e) => {
-                    tracing::warn!("Failed to parse post {:?}: {}", path, e);
+                    tracing::warn!("Failed to parse project {:?}: {}", path, e);
                 }
             }
         }
     }
 
-    Ok(posts)
+    Ok(projects)
 }
 
-/// Load all projects from a directory
-pub async fn load_projects(dir: &Path) -> Result<HashMap<String, Project>> {
-    let mut projects = HashMap::new();
+/// Load all entries of a collection from a directory
+pub async fn load_entries(dir: &Path) -> Result<HashMap<String, Entry>> {
+    let mut entries_by_slug = HashMap::new();
 
     let mut entries = fs::read_dir(dir).await?;
     while let Some(entry) = entries.next_entry().await? {
@@ -186,24 +415,127 @@ pub async fn load_projects(dir: &Path) -> Result<HashMap<String, Project>> {
         if path.extension().is_some_and(|e| e == "md") {
             let content = fs::read_to_string(&path).await?;
 
-            match parse_frontmatter::<ProjectMetadata>(&content) {
+            match parse_frontmatter::<EntryMetadata>(&content) {
                 Ok((metadata, body)) => {
-                    let html = render_markdown(&body);
+                    let entry = Entry {
+                        content_html: render_markdown(&body),
+                        content_raw: body,
+                        metadata: metadata.clone(),
+                    };
 
-                    let project = Project {
+                    entries_by_slug.insert(metadata.slug.clone(), entry);
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to parse entry {:?}: {}", path, e);
+                }
+            }
+        }
+    }
+
+    Ok(entries_by_slug)
+}
+
+/// Load all standalone pages from a directory, keyed by their normalized path
+pub async fn load_pages(dir: &Path) -> Result<HashMap<String, Page>> {
+    let mut pages = HashMap::new();
+
+    let mut entries = fs::read_dir(dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+
+        if path.extension().is_some_and(|e| e == "md") {
+            let content = fs::read_to_string(&path).await?;
+
+            match parse_frontmatter::<PageMetadata>(&content) {
+                Ok((mut metadata, body)) => {
+                    metadata.path = format!("/{}", metadata.path.trim_matches('/'));
+                    if pages.contains_key(&metadata.path) {
+                        tracing::warn!(
+                            "Page {:?} has the same path as another page, skipping",
+                            path
+                        );
+                        continue;
+                    }
+
+                    let page = Page {
+                        content_html: render_markdown(&body),
                         metadata: metadata.clone(),
-                        content_raw: body,
-                        content_html: html,
                     };
 
-                    projects.insert(metadata.slug.clone(), project);
+                    pages.insert(metadata.path, page);
                 }
                 Err(e) => {
-                    tracing::warn!("Failed to parse project {:?}: {}", path, e);
+                    tracing::warn!("Failed to parse page {:?}: {}", path, e);
                 }
             }
         }
     }
 
-    Ok(projects)
+    Ok(pages)
+}
+
+/// Load all author profiles from a directory
+pub async fn load_authors(dir: &Path) -> Result<HashMap<String, Author>> {
+    let mut authors = HashMap::new();
+
+    let mut entries = fs::read_dir(dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+
+        if path.extension().is_some_and(|e| e == "md") {
+            let content = fs::read_to_string(&path).await?;
+
+            match parse_frontmatter::<AuthorMetadata>(&content) {
+                Ok((metadata, body)) => {
+                    let author = Author {
+                        content_html: render_markdown(&body),
+                        metadata: metadata.clone(),
+                    };
+
+                    authors.insert(metadata.slug.clone(), author);
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to parse author {:?}: {}", path, e);
+                }
+            }
+        }
+    }
+
+    Ok(authors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simple_frontmatter_nested_map() {
+        let (metadata, body) = parse_frontmatter::<EntryMetadata>(
+            "---\ntitle: Rust at scale\nslug: rust-at-scale\nweight: 3\nextra:\n  venue: RustConf\n  slides: [a.pdf, b.pdf]\ntags: [rust]\n---\nBody",
+        )
+        .unwrap();
+
+        assert_eq!(body, "Body");
+        assert_eq!(metadata.weight, Some(3));
+        assert_eq!(metadata.tags, ["rust"]);
+        assert_eq!(metadata.extra["venue"], "RustConf");
+        assert_eq!(metadata.extra.len(), 2);
+    }
+
+    #[test]
+    fn test_bundle_asset_urls() {
+        let html = render_bundle_markdown(
+            "![Diagram](diagram.png) ![Logo](/static/logo.svg) ![Remote](https://example.com/a.png)\n\n\
+             [slides](./files/slides.pdf) [next post](other-post) [top](#intro) [mail](mailto:a@b.c)",
+            "/blog/hello/assets",
+        );
+
+        assert!(html.contains(r#"src="/blog/hello/assets/diagram.png""#));
+        assert!(html.contains(r#"src="/static/logo.svg""#));
+        assert!(html.contains(r#"src="https://example.com/a.png""#));
+        assert!(html.contains(r#"href="/blog/hello/assets/files/slides.pdf""#));
+        assert!(html.contains(r#"href="other-post""#));
+        assert!(html.contains(r##"href="#intro""##));
+        assert!(html.contains(r#"href="mailto:a@b.c""#));
+    }
 }
@@ -1,28 +1,132 @@
+pub mod archive;
+pub mod collections;
+pub mod history;
 pub mod markdown;
+pub mod redirects;
+pub mod search;
+pub mod tags;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
+use std::sync::OnceLock;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use tracing::info;
 
-use crate::models::{Post, Project};
+use crate::i18n::Lang;
+use crate::models::{Author, Page, Post, Project, Resume};
+
+use self::archive::ArchiveYear;
+use self::collections::Collection;
+use self::redirects::{RedirectSource, Redirects, RedirectsFile, Target};
+use self::search::SearchIndex;
+use self::tags::{Tag, TagIndex, TagLink};
 
 /// In-memory store for all content
 #[derive(Debug, Default)]
 pub struct ContentStore {
     pub posts: HashMap<String, Post>,
     pub projects: HashMap<String, Project>,
+    pub authors: HashMap<String, Author>,
+    /// Collections from `collections.yaml`, sorted by name
+    pub collections: Vec<Collection>,
+    /// Standalone pages keyed by their path
+    pub pages: HashMap<String, Page>,
+    /// Resume from `resume.yaml`, if present
+    pub resume: Option<Resume>,
+    /// Redirects from `aliases` and `redirects.toml`
+    pub redirects: Redirects,
+    pub search: SearchIndex,
+    /// Tags of published posts, per language
+    pub tags: HashMap<String, TagIndex>,
+    /// Language whose posts are served without a URL prefix
+    pub default_language: String,
+}
+
+/// First path segments owned by built-in routes
+const RESERVED_SEGMENTS: &[&str] = &[
+    "",
+    "blog",
+    "projects",
+    "resume",
+    "resume.json",
+    "resume.vcf",
+    "contact",
+    "search",
+    "authors",
+    "lang",
+    "static",
+    "health",
+    "admin",
+    "preview",
+    "og",
+    "views",
+    "webmention",
+    "webmentions",
+    "sitemap.xml",
+    "sitemaps",
+    "robots.txt",
+    "rss.xml",
+    "atom.xml",
+    "feed.json",
+    "favicon.ico",
+];
+
+/// Fixed paths of built-in routes outside the per-language blog
+const BUILTIN_PATHS: &[&str] = &[
+    "/",
+    "/blog/tags",
+    "/blog/archive",
+    "/projects",
+    "/projects/rss.xml",
+    "/resume",
+    "/resume/print",
+    "/resume.json",
+    "/resume.vcf",
+    "/contact",
+    "/search",
+    "/sitemap.xml",
+    "/robots.txt",
+    "/favicon.ico",
+    "/health",
+    "/webmention",
+    "/og/site.png",
+];
+
+/// Check whether a path from content would be shadowed by a built-in route
+/// or a language prefix
+pub(crate) fn is_reserved_path(path: &str, languages: &[String]) -> bool {
+    let first_segment = path
+        .trim_start_matches('/')
+        .split('/')
+        .next()
+        .unwrap_or_default();
+    RESERVED_SEGMENTS.contains(&first_segment) || languages.iter().any(|l| l == first_segment)
+}
+
+/// Key of a post in [`ContentStore::posts`]: the bare slug for the default
+/// language, `<lang>/<slug>` for other languages
+pub fn post_key(lang: &str, slug: &str, default_language: &str) -> String {
+    if lang == default_language {
+        slug.to_string()
+    } else {
+        format!("{}/{}", lang, slug)
+    }
 }
 
 impl ContentStore {
-    /// Load all content from the filesystem
-    pub async fn load(content_dir: &str) -> Result<Self> {
-        let mut store = Self::default();
+    /// Load all content from the filesystem. The first language is the
+    /// default one.
+    pub async fn load(content_dir: &str, languages: &[String]) -> Result<Self> {
+        let default_language = languages[0].as_str();
+        let mut store = Self {
+            default_language: default_language.to_string(),
+            ..Self::default()
+        };
 
         let blog_dir = Path::new(content_dir).join("blog");
         if blog_dir.exists() {
-            store.posts = markdown::load_posts(&blog_dir).await?;
+            store.posts = markdown::load_posts(&blog_dir, languages).await?;
             info!("Loaded {} blog posts", store.posts.len());
         }
 
@@ -32,20 +136,269 @@ impl ContentStore {
             info!("Loaded {} projects", store.projects.len());
         }
 
+        if let Some(history) = history::GitHistory::load(Path::new(content_dir)).await {
+            let content_dir = Path::new(content_dir);
+            for post in store.posts.values_mut() {
+                post.revisions = history.revisions(content_dir, &post.source);
+                post.updated = post
+                    .metadata
+                    .updated
+                    .or_else(|| history::last_updated(&post.revisions));
+            }
+            for project in store.projects.values_mut() {
+                let revisions = history.revisions(content_dir, &project.source);
+                project.updated = project
+                    .metadata
+                    .updated
+                    .or_else(|| history::last_updated(&revisions));
+            }
+            info!("Loaded edit history from git");
+        }
+
+        let authors_dir = Path::new(content_dir).join("authors");
+        if authors_dir.exists() {
+            store.authors = markdown::load_authors(&authors_dir).await?;
+            info!("Loaded {} authors", store.authors.len());
+        }
+        for post in store.posts.values() {
+            for slug in &post.metadata.authors {
+                if !store.authors.contains_key(slug) {
+                    tracing::warn!("Post {:?} names unknown author {:?}", post.source, slug);
+                }
+            }
+        }
+
+        store.collections = collections::load(Path::new(content_dir), languages).await?;
+        for collection in &store.collections {
+            info!(
+                "Loaded {} entries in collection {}",
+                collection.entries.len(),
+                collection.name
+            );
+        }
+
+        let pages_dir = Path::new(content_dir).join("pages");
+        if pages_dir.exists() {
+            store.pages = markdown::load_pages(&pages_dir).await?;
+            store.pages.retain(|path, _| {
+                let reserved = is_reserved_path(path, languages);
+                let taken = store
+                    .collections
+                    .iter()
+                    .any(|c| path == &c.path || path.starts_with(&format!("{}/", c.path)));
+                if reserved || taken {
+                    tracing::warn!("Page path {:?} is used by another route, skipping", path);
+                }
+                !reserved && !taken
+            });
+            info!("Loaded {} pages", store.pages.len());
+        }
+
+        let resume_path = Path::new(content_dir).join("resume.yaml");
+        if resume_path.exists() {
+            let resume = tokio::fs::read_to_string(&resume_path).await?;
+            store.resume = Some(
+                serde_yaml::from_str(&resume)
+                    .with_context(|| format!("Failed to parse {:?}", resume_path))?,
+            );
+            info!("Loaded resume");
+        }
+
+        let tag_meta = tags::load_meta(&Path::new(content_dir).join("tags.yaml")).await?;
+        for lang in languages {
+            let index =
+                TagIndex::build(store.posts.values().filter(|p| &p.lang == lang), &tag_meta);
+            info!("Indexed {} tags in {}", index.len(), lang);
+            store.tags.insert(lang.clone(), index);
+        }
+
+        let redirects_file =
+            RedirectsFile::load(&Path::new(content_dir).join("redirects.toml")).await?;
+        store.redirects = Redirects::build(
+            store.alias_sources(redirects_file.into_sources()),
+            &store.live_paths(languages),
+        );
+        info!("Loaded {} redirects", store.redirects.len());
+
+        store.search = SearchIndex::build(store.posts.values(), store.projects.values());
+        info!("Indexed {} documents for search", store.search.len());
+
         Ok(store)
     }
 
-    /// Get all published posts sorted by date (newest first)
-    pub fn published_posts(&self) -> Vec<&Post> {
+    /// Redirect sources from post and project `aliases`, followed by `extra`.
+    /// A bare alias is a slug in the same section (and language) as the item.
+    fn alias_sources(&self, extra: Vec<RedirectSource>) -> Vec<RedirectSource> {
+        let alias_path = |alias: &str, section: &str| {
+            if alias.starts_with('/') {
+                alias.to_string()
+            } else {
+                format!("{}/{}", section, alias)
+            }
+        };
+
         let mut posts: Vec<_> = self.posts.values().filter(|p| !p.metadata.draft).collect();
-        posts.sort_by(|a, b| b.metadata.date.cmp(&a.metadata.date));
+        posts.sort_by(|a, b| a.path.cmp(&b.path));
+        let post_aliases = posts.into_iter().flat_map(|post| {
+            let section = post.path.rsplit_once('/').map_or("", |(parent, _)| parent);
+            post.metadata
+                .aliases
+                .iter()
+                .map(move |alias| RedirectSource {
+                    from: alias_path(alias, section),
+                    target: Target::Moved(post.path.clone()),
+                    origin: format!("aliases of post {:?}", post.path),
+                })
+        });
+
+        let project_aliases = self.all_projects().into_iter().flat_map(|project| {
+            let path = format!("/projects/{}", project.metadata.slug);
+            project
+                .metadata
+                .aliases
+                .iter()
+                .map(move |alias| RedirectSource {
+                    from: alias_path(alias, "/projects"),
+                    target: Target::Moved(path.clone()),
+                    origin: format!("aliases of project {:?}", path),
+                })
+        });
+
+        post_aliases.chain(project_aliases).chain(extra).collect()
+    }
+
+    /// Paths served by built-in routes and real content, which redirects
+    /// may not shadow
+    fn live_paths(&self, languages: &[String]) -> HashSet<String> {
+        let mut paths: HashSet<String> = BUILTIN_PATHS.iter().map(|p| p.to_string()).collect();
+        for lang in languages {
+            let prefix = Lang::new(lang, &self.default_language).prefix;
+            for path in ["/blog", "/rss.xml", "/atom.xml", "/feed.json"] {
+                paths.insert(format!("{}{}", prefix, path));
+            }
+            paths.extend(
+                self.all_tags_in(lang)
+                    .into_iter()
+                    .map(|tag| format!("{}/blog/tag/{}", prefix, tag.slug)),
+            );
+        }
+        for year in self.archive() {
+            paths.insert(format!("/blog/{}", year.year));
+            paths.extend(
+                year.months
+                    .iter()
+                    .map(|m| format!("/blog/{}/{:02}", m.year, m.month)),
+            );
+        }
+        paths.extend(self.authors.keys().map(|slug| format!("/authors/{}", slug)));
+        paths.extend(self.posts.values().map(|p| p.path.clone()));
+        paths.extend(
+            self.projects
+                .keys()
+                .map(|slug| format!("/projects/{}", slug)),
+        );
+        paths.extend(self.pages.keys().cloned());
+        for collection in &self.collections {
+            paths.insert(collection.path.clone());
+            paths.extend(
+                collection
+                    .entries
+                    .keys()
+                    .map(|slug| collection.entry_path(slug)),
+            );
+        }
+        paths
+    }
+
+    /// Look up a published page by path
+    pub fn page(&self, path: &str) -> Option<&Page> {
+        self.pages.get(path).filter(|p| !p.metadata.draft)
+    }
+
+    /// Look up the collection mounted at a URL prefix
+    pub fn collection_at(&self, path: &str) -> Option<&Collection> {
+        self.collections.iter().find(|c| c.path == path)
+    }
+
+    /// Get published posts in the default language sorted by date (newest first)
+    pub fn published_posts(&self) -> Vec<&Post> {
+        self.published_posts_in(&self.default_language)
+    }
+
+    /// Get published posts in a language sorted by date (newest first)
+    pub fn published_posts_in(&self, lang: &str) -> Vec<&Post> {
+        let mut posts: Vec<_> = self
+            .posts
+            .values()
+            .filter(|p| !p.metadata.draft && p.lang == lang)
+            .collect();
+        posts.sort_by_key(|p| std::cmp::Reverse(p.metadata.date));
+        posts
+    }
+
+    /// Look up a post by language and slug
+    pub fn post(&self, lang: &str, slug: &str) -> Option<&Post> {
+        self.posts
+            .get(&post_key(lang, slug, &self.default_language))
+    }
+
+    /// Key of a post in `posts`, also used for its view counter
+    pub fn key_of(&self, post: &Post) -> String {
+        post_key(&post.lang, &post.metadata.slug, &self.default_language)
+    }
+
+    /// Get all published versions of a post (including itself), the default
+    /// language first
+    pub fn translations(&self, post: &Post) -> Vec<&Post> {
+        let mut posts: Vec<_> = self
+            .posts
+            .values()
+            .filter(|p| !p.metadata.draft && p.translation_key == post.translation_key)
+            .collect();
+        posts.sort_by_key(|p| (p.lang != self.default_language, p.lang.clone()));
         posts
     }
 
+    /// Get published posts grouped by year and month (newest first)
+    pub fn archive(&self) -> Vec<ArchiveYear<'_>> {
+        archive::group(&self.published_posts())
+    }
+
+    /// Resolve a post's authors. Posts without an `authors` field belong to
+    /// the default author, if one is configured.
+    pub fn post_authors(&self, post: &Post, default_author: Option<&str>) -> Vec<&Author> {
+        if post.metadata.authors.is_empty() {
+            return default_author
+                .and_then(|slug| self.authors.get(slug))
+                .into_iter()
+                .collect();
+        }
+
+        post.metadata
+            .authors
+            .iter()
+            .filter_map(|slug| self.authors.get(slug))
+            .collect()
+    }
+
+    /// Get published posts written by an author, sorted by date (newest first)
+    pub fn posts_by_author(&self, slug: &str, default_author: Option<&str>) -> Vec<&Post> {
+        self.published_posts()
+            .into_iter()
+            .filter(|p| {
+                if p.metadata.authors.is_empty() {
+                    default_author == Some(slug)
+                } else {
+                    p.metadata.authors.iter().any(|a| a == slug)
+                }
+            })
+            .collect()
+    }
+
     /// Get all projects sorted by date (newest first)
     pub fn all_projects(&self) -> Vec<&Project> {
         let mut projects: Vec<_> = self.projects.values().collect();
-        projects.sort_by(|a, b| b.metadata.date.cmp(&a.metadata.date));
+        projects.sort_by_key(|p| std::cmp::Reverse(p.metadata.date));
         projects
     }
 
@@ -57,33 +410,158 @@ impl ContentStore {
             .collect()
     }
 
-    /// Get all unique tags from published posts, sorted alphabetically
-    pub fn all_tags(&self) -> Vec<String> {
-        let mut tags: Vec<String> = self
-            .published_posts()
-            .iter()
-            .flat_map(|p| p.metadata.tags.iter().cloned())
-            .collect();
-        tags.sort();
-        tags.dedup();
-        tags
+    /// Tags used by published posts in a language; empty for languages
+    /// without posts
+    pub fn tags_in(&self, lang: &str) -> &TagIndex {
+        static EMPTY: OnceLock<TagIndex> = OnceLock::new();
+        self.tags
+            .get(lang)
+            .unwrap_or_else(|| EMPTY.get_or_init(TagIndex::default))
     }
 
-    /// Get published posts with a specific tag, sorted by date (newest first)
+    /// Get all tags used by published posts in the default language, sorted by slug
+    pub fn all_tags(&self) -> Vec<&Tag> {
+        self.all_tags_in(&self.default_language)
+    }
+
+    /// Same as [`Self::all_tags`] for posts in a given language
+    pub fn all_tags_in(&self, lang: &str) -> Vec<&Tag> {
+        self.tags_in(lang).all().collect()
+    }
+
+    /// Resolve a post's frontmatter tags to canonical tag links
+    pub fn tag_links(&self, post: &Post) -> Vec<TagLink<'_>> {
+        self.tags_in(&post.lang).links(&post.metadata.tags)
+    }
+
+    /// Get published posts in the default language with a specific tag or
+    /// any of its child tags, sorted by date (newest first). The tag may be
+    /// given in any spelling or as an alias.
     pub fn posts_by_tag(&self, tag: &str) -> Vec<&Post> {
-        let tag_lower = tag.to_lowercase();
+        self.posts_by_tag_in(&self.default_language, tag)
+    }
+
+    /// Same as [`Self::posts_by_tag`] for posts in a given language
+    pub fn posts_by_tag_in(&self, lang: &str, tag: &str) -> Vec<&Post> {
+        let tags = self.tags_in(lang);
+        let slug = tags.canonical(tag);
         let mut posts: Vec<_> = self
             .posts
             .values()
             .filter(|p| {
                 !p.metadata.draft
-                    && p.metadata
-                        .tags
-                        .iter()
-                        .any(|t| t.to_lowercase() == tag_lower)
+                    && p.lang == lang
+                    && p.metadata.tags.iter().any(|t| tags.is_within(t, &slug))
             })
             .collect();
-        posts.sort_by(|a, b| b.metadata.date.cmp(&a.metadata.date));
+        posts.sort_by_key(|p| std::cmp::Reverse(p.metadata.date));
         posts
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_pages_skip_reserved_paths() {
+        let dir = std::env::temp_dir().join(format!("nebula-pages-{}", std::process::id()));
+        let pages_dir = dir.join("pages");
+        tokio::fs::create_dir_all(&pages_dir).await.unwrap();
+        for (file, path, draft) in [
+            ("uses.md", "/uses/", false),
+            ("feed.md", "/rss.xml", false),
+            ("cards.md", "/og/cards", false),
+            ("russian.md", "/ru/about", false),
+            ("soon.md", "/soon", true),
+        ] {
+            let page = format!(
+                "---\ntitle: Page\npath: {}\ndraft: {}\n---\n\nBody\n",
+                path, draft
+            );
+            tokio::fs::write(pages_dir.join(file), page).await.unwrap();
+        }
+
+        let languages = ["en".to_string(), "ru".to_string()];
+        let store = ContentStore::load(dir.to_str().unwrap(), &languages).await;
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+        let store = store.unwrap();
+
+        // What `routes::pages::fallback` serves
+        assert!(store.page("/uses").is_some());
+        assert!(store.page("/soon").is_none());
+        let mut loaded: Vec<_> = store.pages.keys().map(String::as_str).collect();
+        loaded.sort();
+        assert_eq!(loaded, ["/soon", "/uses"]);
+    }
+
+    #[tokio::test]
+    async fn test_post_authors_fall_back_to_default() {
+        let dir = std::env::temp_dir().join(format!("nebula-authors-{}", std::process::id()));
+        for (file, source) in [
+            (
+                "authors/alice.md",
+                "---\nname: Alice\nslug: alice\nbio: null\n---\n",
+            ),
+            (
+                "authors/bob.md",
+                "---\nname: Bob\nslug: bob\nbio: null\n---\n",
+            ),
+            (
+                "blog/guest.md",
+                "---\ntitle: Guest\nslug: guest\ndate: 2025-03-01T10:00:00Z\ntags: []\n\
+                 draft: false\nauthors: [bob, ghost, alice]\n---\n",
+            ),
+            (
+                "blog/own.md",
+                "---\ntitle: Own\nslug: own\ndate: 2025-03-02T10:00:00Z\ntags: []\n\
+                 draft: false\n---\n",
+            ),
+        ] {
+            let path = dir.join(file);
+            tokio::fs::create_dir_all(path.parent().unwrap())
+                .await
+                .unwrap();
+            tokio::fs::write(path, source).await.unwrap();
+        }
+
+        let store = ContentStore::load(dir.to_str().unwrap(), &["en".to_string()]).await;
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+        let store = store.unwrap();
+        let names = |slug: &str, default: Option<&str>| -> Vec<String> {
+            let post = store.post("en", slug).unwrap();
+            store
+                .post_authors(post, default)
+                .into_iter()
+                .map(|a| a.metadata.name.clone())
+                .collect()
+        };
+
+        // Unknown slugs are skipped, the rest keep frontmatter order
+        assert_eq!(names("guest", Some("alice")), ["Bob", "Alice"]);
+        assert_eq!(names("own", Some("alice")), ["Alice"]);
+        assert!(names("own", None).is_empty());
+        assert!(names("own", Some("ghost")).is_empty());
+    }
+
+    #[test]
+    fn test_live_paths_include_builtin_routes() {
+        let store = ContentStore {
+            default_language: "en".into(),
+            ..ContentStore::default()
+        };
+        let paths = store.live_paths(&["en".to_string(), "ru".to_string()]);
+
+        for path in [
+            "/rss.xml",
+            "/blog/tags",
+            "/sitemap.xml",
+            "/blog",
+            "/ru/blog",
+            "/ru/feed.json",
+        ] {
+            assert!(paths.contains(path), "{} is missing", path);
+        }
+        assert!(!paths.contains("/ru/blog/tags"));
+    }
+}
@@ -0,0 +1,215 @@
+//! Redirect table built from post and project `aliases` and
+//! `content/redirects.toml`.
+//!
+//! Entries that would shadow a real page, duplicates and redirect loops are
+//! dropped with a warning when content is loaded; chains are collapsed so
+//! every redirect takes a single hop.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use tokio::fs;
+
+/// What an old URL resolves to
+#[derive(Debug, Clone, PartialEq)]
+pub enum Target {
+    /// 301 to a site-relative path or an absolute URL
+    Moved(String),
+    /// 410 Gone
+    Gone,
+}
+
+/// A redirect before validation; `origin` names its source in warnings
+#[derive(Debug)]
+pub struct RedirectSource {
+    pub from: String,
+    pub target: Target,
+    pub origin: String,
+}
+
+/// `content/redirects.toml`
+#[derive(Debug, Default, Deserialize)]
+pub struct RedirectsFile {
+    /// Old path → new path or URL
+    #[serde(default)]
+    pub redirects: BTreeMap<String, String>,
+    /// Paths that answer 410 Gone
+    #[serde(default)]
+    pub gone: Vec<String>,
+}
+
+impl RedirectsFile {
+    /// Read the redirects file; a missing file means no redirects
+    pub async fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path).await?;
+        toml::from_str(&content).with_context(|| format!("Failed to parse {:?}", path))
+    }
+
+    pub fn into_sources(self) -> Vec<RedirectSource> {
+        let redirects = self.redirects.into_iter().map(|(from, to)| RedirectSource {
+            from,
+            target: Target::Moved(to),
+            origin: "redirects.toml".to_string(),
+        });
+        let gone = self.gone.into_iter().map(|from| RedirectSource {
+            from,
+            target: Target::Gone,
+            origin: "redirects.toml".to_string(),
+        });
+        redirects.chain(gone).collect()
+    }
+}
+
+/// Validated redirects keyed by normalized path
+#[derive(Debug, Default)]
+pub struct Redirects {
+    entries: HashMap<String, Target>,
+}
+
+/// Leading slash, no trailing slash (except for the root)
+pub fn normalize_path(path: &str) -> String {
+    let trimmed = path.trim().trim_matches('/');
+    format!("/{}", trimmed)
+}
+
+fn is_internal(target: &str) -> bool {
+    target.starts_with('/') && !target.starts_with("//")
+}
+
+impl Redirects {
+    /// Validate redirect sources against the paths of real content
+    pub fn build(sources: Vec<RedirectSource>, live_paths: &HashSet<String>) -> Self {
+        let mut entries: HashMap<String, Target> = HashMap::new();
+
+        for source in sources {
+            let from = normalize_path(&source.from);
+            if live_paths.contains(&from) {
+                tracing::warn!(
+                    "Redirect from {:?} ({}) would shadow an existing page, skipping",
+                    from,
+                    source.origin
+                );
+                continue;
+            }
+            if entries.contains_key(&from) {
+                tracing::warn!(
+                    "Duplicate redirect from {:?} ({}), skipping",
+                    from,
+                    source.origin
+                );
+                continue;
+            }
+            entries.insert(from, source.target);
+        }
+
+        // Follow chains to their end; anything that comes back to a visited
+        // path is a loop
+        let mut resolved = HashMap::with_capacity(entries.len());
+        for (from, target) in &entries {
+            let mut visited = HashSet::from([from.clone()]);
+            let mut current = target.clone();
+            let result = loop {
+                let Target::Moved(to) = &current else {
+                    break Some(current);
+                };
+                if !is_internal(to) {
+                    break Some(current);
+                }
+                let key = normalize_path(to.split(['?', '#']).next().unwrap_or_default());
+                if !visited.insert(key.clone()) {
+                    break None;
+                }
+                match entries.get(&key) {
+                    Some(next) => current = next.clone(),
+                    None => break Some(current),
+                }
+            };
+
+            match result {
+                Some(target) => {
+                    resolved.insert(from.clone(), target);
+                }
+                None => tracing::warn!("Redirect from {:?} ends in a loop, skipping", from),
+            }
+        }
+
+        Self { entries: resolved }
+    }
+
+    pub fn get(&self, path: &str) -> Option<&Target> {
+        self.entries.get(&normalize_path(path))
+    }
+
+    /// All redirects sorted by source path
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Target)> {
+        let mut entries: Vec<_> = self.entries.iter().map(|(k, v)| (k.as_str(), v)).collect();
+        entries.sort_by_key(|(from, _)| *from);
+        entries.into_iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn moved(from: &str, to: &str) -> RedirectSource {
+        RedirectSource {
+            from: from.to_string(),
+            target: Target::Moved(to.to_string()),
+            origin: "test".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_build_validates_and_collapses() {
+        let file: RedirectsFile = toml::from_str(
+            "gone = [\"/removed\"]\n\n[redirects]\n\"/old\" = \"/older/\"\n\"/older\" = \"/blog/new\"\n",
+        )
+        .unwrap();
+        let mut sources = file.into_sources();
+        sources.extend([
+            moved("/blog/live", "/elsewhere"),
+            moved("/a", "/b"),
+            moved("/b", "/a"),
+            moved("/old/", "/somewhere-else"),
+            moved("/to-gone", "/removed"),
+            moved("/external", "https://example.com/x"),
+        ]);
+        let live = HashSet::from(["/blog/live".to_string(), "/blog/new".to_string()]);
+
+        let redirects = Redirects::build(sources, &live);
+
+        assert_eq!(
+            redirects.get("/old/"),
+            Some(&Target::Moved("/blog/new".into()))
+        );
+        assert_eq!(
+            redirects.get("/older"),
+            Some(&Target::Moved("/blog/new".into()))
+        );
+        assert_eq!(redirects.get("/removed"), Some(&Target::Gone));
+        assert_eq!(redirects.get("/to-gone"), Some(&Target::Gone));
+        assert_eq!(
+            redirects.get("/external"),
+            Some(&Target::Moved("https://example.com/x".into()))
+        );
+        assert_eq!(redirects.get("/blog/live"), None);
+        assert_eq!(redirects.get("/a"), None);
+        assert_eq!(redirects.get("/b"), None);
+        assert_eq!(redirects.len(), 5);
+    }
+}
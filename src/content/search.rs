@@ -0,0 +1,466 @@
+//! In-memory full-text search index over posts and projects.
+//!
+//! Built once in `ContentStore::load`, so searching never touches the filesystem.
+//! Words are stemmed with the English or Russian Snowball stemmer depending on
+//! the script they are written in.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::ops::Bound;
+
+use rust_stemmers::{Algorithm, Stemmer};
+
+use crate::models::{Post, Project};
+
+/// Field weights used when scoring a match
+const TITLE_WEIGHT: f32 = 5.0;
+const TAG_WEIGHT: f32 = 3.0;
+const DESCRIPTION_WEIGHT: f32 = 2.0;
+const BODY_WEIGHT: f32 = 1.0;
+
+/// Number of words shown on each side of the first match in a snippet
+const SNIPPET_CONTEXT_WORDS: usize = 12;
+
+/// Kind of document stored in the index
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocKind {
+    Post,
+    Project,
+}
+
+impl DocKind {
+    /// Message id of the kind's label
+    pub fn label_id(&self) -> &'static str {
+        match self {
+            DocKind::Post => "search-kind-post",
+            DocKind::Project => "search-kind-project",
+        }
+    }
+}
+
+/// Searchable document
+#[derive(Debug, Clone)]
+pub struct SearchDoc {
+    pub kind: DocKind,
+    pub slug: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub url: String,
+    /// Plain text extracted from the rendered HTML
+    pub text: String,
+}
+
+/// A single search result with a highlighted snippet
+#[derive(Debug)]
+pub struct SearchHit<'a> {
+    pub doc: &'a SearchDoc,
+    pub score: f32,
+    /// HTML-escaped snippet with matches wrapped in `<mark>`
+    pub snippet: String,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Posting {
+    doc: usize,
+    weight: f32,
+}
+
+/// English and Russian stemmers, created once per index
+struct Stemmers {
+    english: Stemmer,
+    russian: Stemmer,
+}
+
+impl Default for Stemmers {
+    fn default() -> Self {
+        Self {
+            english: Stemmer::create(Algorithm::English),
+            russian: Stemmer::create(Algorithm::Russian),
+        }
+    }
+}
+
+impl fmt::Debug for Stemmers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Stemmers")
+    }
+}
+
+impl Stemmers {
+    /// Stem a lowercase word with the stemmer matching its script
+    fn stem(&self, word: &str) -> String {
+        let stemmer = if word.chars().any(is_cyrillic) {
+            &self.russian
+        } else {
+            &self.english
+        };
+        stemmer.stem(word).into_owned()
+    }
+}
+
+/// Inverted index from stemmed terms to weighted postings. Terms are sorted,
+/// so prefix matches are a range lookup.
+#[derive(Debug, Default)]
+pub struct SearchIndex {
+    docs: Vec<SearchDoc>,
+    terms: BTreeMap<String, Vec<Posting>>,
+    stemmers: Stemmers,
+}
+
+impl SearchIndex {
+    /// Build the index from published posts and all projects
+    pub fn build<'a>(
+        posts: impl IntoIterator<Item = &'a Post>,
+        projects: impl IntoIterator<Item = &'a Project>,
+    ) -> Self {
+        let mut index = Self::default();
+
+        for post in posts.into_iter().filter(|p| !p.metadata.draft) {
+            index.add(
+                SearchDoc {
+                    kind: DocKind::Post,
+                    slug: post.metadata.slug.clone(),
+                    title: post.metadata.title.clone(),
+                    description: post.metadata.description.clone(),
+                    url: post.path.clone(),
+                    text: html_to_text(&post.content_html),
+                },
+                &post.metadata.tags,
+            );
+        }
+
+        for project in projects {
+            index.add(
+                SearchDoc {
+                    kind: DocKind::Project,
+                    slug: project.metadata.slug.clone(),
+                    title: project.metadata.title.clone(),
+                    description: project.metadata.description.clone(),
+                    url: format!("/projects/{}", project.metadata.slug),
+                    text: html_to_text(&project.content_html),
+                },
+                &project.metadata.tags,
+            );
+        }
+
+        index
+    }
+
+    /// Number of indexed documents
+    pub fn len(&self) -> usize {
+        self.docs.len()
+    }
+
+    /// Check if the index has no documents
+    pub fn is_empty(&self) -> bool {
+        self.docs.is_empty()
+    }
+
+    fn add(&mut self, doc: SearchDoc, tags: &[String]) {
+        let id = self.docs.len();
+        let mut weights: HashMap<String, f32> = HashMap::new();
+
+        let stemmers = &self.stemmers;
+        let mut add_field = |text: &str, weight: f32| {
+            for (_, word) in words(text) {
+                *weights.entry(stemmers.stem(&word)).or_default() += weight;
+            }
+        };
+
+        add_field(&doc.title, TITLE_WEIGHT);
+        for tag in tags {
+            add_field(tag, TAG_WEIGHT);
+        }
+        if let Some(desc) = &doc.description {
+            add_field(desc, DESCRIPTION_WEIGHT);
+        }
+        add_field(&doc.text, BODY_WEIGHT);
+
+        for (term, weight) in weights {
+            // Dampen long documents repeating the same word
+            let weight = 1.0 + weight.ln_1p();
+            self.terms
+                .entry(term)
+                .or_default()
+                .push(Posting { doc: id, weight });
+        }
+
+        self.docs.push(doc);
+    }
+
+    /// Search the index. Every query word must match; the last word is also
+    /// matched as a prefix so results update while the user is still typing.
+    pub fn search(&self, query: &str, limit: usize) -> Vec<SearchHit<'_>> {
+        let query_words: Vec<String> = words(query).into_iter().map(|(_, w)| w).collect();
+        if query_words.is_empty() {
+            return Vec::new();
+        }
+
+        let total_docs = self.docs.len() as f32;
+        let mut scores: HashMap<usize, f32> = HashMap::new();
+        let mut matchers = Vec::with_capacity(query_words.len());
+
+        for (i, word) in query_words.iter().enumerate() {
+            let is_last = i == query_words.len() - 1;
+            let matcher = Matcher::new(word, is_last, &self.stemmers);
+
+            let mut word_scores: HashMap<usize, f32> = HashMap::new();
+            for postings in self.matching_terms(&matcher) {
+                let idf = (1.0 + total_docs / postings.len() as f32).ln();
+                for posting in postings {
+                    let score = word_scores.entry(posting.doc).or_default();
+                    *score = score.max(posting.weight * idf);
+                }
+            }
+
+            // AND semantics: keep only documents matched by every word
+            if i == 0 {
+                scores = word_scores;
+            } else {
+                scores.retain(|doc, _| word_scores.contains_key(doc));
+                for (doc, score) in scores.iter_mut() {
+                    *score += word_scores[doc];
+                }
+            }
+
+            matchers.push(matcher);
+        }
+
+        let mut hits: Vec<_> = scores
+            .into_iter()
+            .map(|(doc, score)| {
+                let doc = &self.docs[doc];
+                let source = doc
+                    .description
+                    .as_deref()
+                    .filter(|d| contains_match(d, &matchers))
+                    .unwrap_or(&doc.text);
+                SearchHit {
+                    doc,
+                    score,
+                    snippet: snippet(source, &matchers),
+                }
+            })
+            .collect();
+
+        hits.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.doc.title.cmp(&b.doc.title))
+        });
+        hits.truncate(limit);
+        hits
+    }
+
+    /// Postings of the terms a query word matches: its stem, and for a
+    /// prefix matcher every term starting with the word
+    fn matching_terms<'a>(&'a self, matcher: &'a Matcher) -> impl Iterator<Item = &'a [Posting]> {
+        let exact = self.terms.get(&matcher.stem).map(Vec::as_slice);
+        let prefixed = matcher.prefix.iter().flat_map(move |prefix| {
+            self.terms
+                .range::<str, _>((Bound::Included(prefix.as_str()), Bound::Unbounded))
+                .take_while(move |(term, _)| term.starts_with(prefix.as_str()))
+                .filter(move |(term, _)| **term != matcher.stem)
+                .map(|(_, postings)| postings.as_slice())
+        });
+        exact.into_iter().chain(prefixed)
+    }
+}
+
+/// Matches text words against one query word
+struct Matcher<'a> {
+    stem: String,
+    prefix: Option<String>,
+    stemmers: &'a Stemmers,
+}
+
+impl<'a> Matcher<'a> {
+    fn new(word: &str, allow_prefix: bool, stemmers: &'a Stemmers) -> Self {
+        Self {
+            stem: stemmers.stem(word),
+            prefix: allow_prefix.then(|| word.to_string()),
+            stemmers,
+        }
+    }
+
+    fn matches_word(&self, word: &str) -> bool {
+        self.prefix.as_deref().is_some_and(|p| word.starts_with(p))
+            || self.stemmers.stem(word) == self.stem
+    }
+}
+
+fn contains_match(text: &str, matchers: &[Matcher]) -> bool {
+    words(text)
+        .iter()
+        .any(|(_, w)| matchers.iter().any(|m| m.matches_word(w)))
+}
+
+/// Split text into lowercase words with their byte ranges in the original text
+fn words(text: &str) -> Vec<(std::ops::Range<usize>, String)> {
+    let mut result = Vec::new();
+    let mut start = None;
+
+    for (i, c) in text.char_indices() {
+        if c.is_alphanumeric() {
+            start.get_or_insert(i);
+        } else if let Some(s) = start.take() {
+            result.push((s..i, text[s..i].to_lowercase()));
+        }
+    }
+    if let Some(s) = start {
+        result.push((s..text.len(), text[s..].to_lowercase()));
+    }
+
+    result
+}
+
+fn is_cyrillic(c: char) -> bool {
+    matches!(c, '\u{0400}'..='\u{04FF}')
+}
+
+/// Build an HTML snippet around the first match, highlighting every match
+fn snippet(text: &str, matchers: &[Matcher]) -> String {
+    let words = words(text);
+    if words.is_empty() {
+        return String::new();
+    }
+
+    let first = words
+        .iter()
+        .position(|(_, w)| matchers.iter().any(|m| m.matches_word(w)))
+        .unwrap_or(0);
+    let from = first.saturating_sub(SNIPPET_CONTEXT_WORDS);
+    let to = (first + SNIPPET_CONTEXT_WORDS + 1).min(words.len());
+
+    let mut out = String::new();
+    if from > 0 {
+        out.push_str("… ");
+    }
+
+    let mut cursor = words[from].0.start;
+    for (range, word) in &words[from..to] {
+        out.push_str(&escape_html(&text[cursor..range.start]));
+        let original = escape_html(&text[range.clone()]);
+        if matchers.iter().any(|m| m.matches_word(word)) {
+            out.push_str("<mark>");
+            out.push_str(&original);
+            out.push_str("</mark>");
+        } else {
+            out.push_str(&original);
+        }
+        cursor = range.end;
+    }
+
+    if to < words.len() {
+        out.push_str(" …");
+    }
+    out
+}
+
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Strip tags from rendered HTML and decode the basic entities
+pub fn html_to_text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+
+    for c in html.chars() {
+        match c {
+            '<' => {
+                in_tag = true;
+                text.push(' ');
+            }
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    let text = text
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&");
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index_of(docs: &[(&str, &str, &str)]) -> SearchIndex {
+        let mut index = SearchIndex::default();
+        for (slug, title, text) in docs {
+            index.add(
+                SearchDoc {
+                    kind: DocKind::Post,
+                    slug: slug.to_string(),
+                    title: title.to_string(),
+                    description: None,
+                    url: format!("/blog/{}", slug),
+                    text: text.to_string(),
+                },
+                &[],
+            );
+        }
+        index
+    }
+
+    #[test]
+    fn test_stemmed_search_ranks_title_higher() {
+        let index = index_of(&[
+            ("body", "Notes", "We were compiling everything by hand"),
+            ("title", "Compilers explained", "Some text"),
+        ]);
+
+        let hits = index.search("compiler", 10);
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].doc.slug, "title");
+        assert!(hits[1].snippet.contains("<mark>compiling</mark>"));
+    }
+
+    #[test]
+    fn test_russian_stemming() {
+        let index = index_of(&[("ru", "Заметки", "Пишем компиляторы на Rust")]);
+        let hits = index.search("компилятор", 10);
+        assert_eq!(hits.len(), 1);
+        assert!(hits[0].snippet.contains("<mark>компиляторы</mark>"));
+    }
+
+    #[test]
+    fn test_prefix_match_on_last_word() {
+        let index = index_of(&[("a", "Async Rust", "Futures and executors")]);
+        assert_eq!(index.search("rust exec", 10).len(), 1);
+        assert!(index.search("exec rust", 10).is_empty());
+    }
+
+    #[test]
+    fn test_snippet_is_escaped() {
+        let index = index_of(&[("a", "Generics", "Use Vec<T> for generics")]);
+        let hits = index.search("generics", 10);
+        assert!(hits[0].snippet.contains("Vec&lt;T&gt;"));
+    }
+
+    #[test]
+    fn test_html_to_text() {
+        assert_eq!(
+            html_to_text("<p>Hello <em>world</em> &amp; &lt;you&gt;</p>"),
+            "Hello world & <you>"
+        );
+    }
+}
@@ -0,0 +1,439 @@
+//! Tag normalization, aliases and per-tag metadata.
+//!
+//! Every tag written in frontmatter is reduced to a canonical URL slug, so
+//! `Rust`, `rust` and `RUST` all end up on `/blog/tag/rust`. Tags can be
+//! nested with `/`: a post tagged `rust/async` is also listed under `rust`.
+//! Optional display names, descriptions and aliases come from `content/tags.yaml`:
+//!
+//! ```yaml
+//! rust:
+//!   name: Rust
+//!   description: Posts about the Rust programming language
+//!   aliases: [rustlang, rust-lang]
+//! rust/async:
+//!   name: Async
+//! ```
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use tokio::fs;
+
+use crate::models::Post;
+
+/// Per-tag settings from `tags.yaml`
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TagMeta {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+}
+
+/// A canonical tag with its published post count.
+/// The count includes posts tagged with any descendant.
+#[derive(Debug, Clone)]
+pub struct Tag {
+    pub slug: String,
+    /// Display name of the last path segment
+    pub name: String,
+    /// Display names of the whole path, e.g. `Rust / Async`
+    pub full_name: String,
+    pub parent: Option<String>,
+    pub description: Option<String>,
+    pub count: usize,
+}
+
+impl Tag {
+    /// Nesting level, 0 for top-level tags
+    pub fn depth(&self) -> usize {
+        self.slug.matches('/').count()
+    }
+}
+
+/// Tag reference rendered next to a post
+#[derive(Debug, Clone, Copy)]
+pub struct TagLink<'a> {
+    pub name: &'a str,
+    pub slug: &'a str,
+}
+
+/// All tags used by published posts, keyed by canonical slug
+#[derive(Debug, Default)]
+pub struct TagIndex {
+    tags: BTreeMap<String, Tag>,
+    aliases: HashMap<String, String>,
+}
+
+impl TagIndex {
+    /// Build the index from posts and optional `tags.yaml` metadata. Without a
+    /// name in metadata, a tag shows the spelling of its oldest post.
+    pub fn build<'a>(
+        posts: impl IntoIterator<Item = &'a Post>,
+        meta: &HashMap<String, TagMeta>,
+    ) -> Self {
+        let mut index = Self::default();
+
+        for (key, tag_meta) in meta {
+            let slug = slugify(key);
+            for alias in &tag_meta.aliases {
+                let alias = slugify(alias);
+                if !alias.is_empty() && alias != slug {
+                    index.aliases.insert(alias, slug.clone());
+                }
+            }
+        }
+        let meta: HashMap<String, &TagMeta> = meta.iter().map(|(k, m)| (slugify(k), m)).collect();
+
+        // Fixed order, so names don't depend on how the caller iterates
+        let mut posts: Vec<&Post> = posts.into_iter().filter(|p| !p.metadata.draft).collect();
+        posts.sort_by_key(|p| (p.metadata.date, &p.path));
+
+        for post in posts {
+            let mut seen = HashSet::new();
+            for raw in &post.metadata.tags {
+                let slug = index.canonical(raw);
+                let raw_segments: Vec<&str> = raw
+                    .split('/')
+                    .map(str::trim)
+                    .filter(|s| !slugify_segment(s).is_empty())
+                    .collect();
+
+                // Count the post once for the tag and each of its ancestors
+                for (depth, node) in ancestors_and_self(&slug).into_iter().enumerate() {
+                    if !seen.insert(node.to_string()) {
+                        continue;
+                    }
+
+                    let tag = index.tags.entry(node.to_string()).or_insert_with(|| {
+                        let tag_meta = meta.get(node);
+                        let segment = node.rsplit('/').next().unwrap_or(node);
+                        // Keep the author's spelling when the raw tag lines up with the slug
+                        let fallback = raw_segments
+                            .get(depth)
+                            .filter(|s| slugify(s) == segment)
+                            .map(|s| s.to_string())
+                            .unwrap_or_else(|| segment.to_string());
+                        Tag {
+                            name: tag_meta.and_then(|m| m.name.clone()).unwrap_or(fallback),
+                            full_name: String::new(),
+                            parent: node.rsplit_once('/').map(|(p, _)| p.to_string()),
+                            description: tag_meta.and_then(|m| m.description.clone()),
+                            slug: node.to_string(),
+                            count: 0,
+                        }
+                    });
+                    tag.count += 1;
+                }
+            }
+        }
+
+        // Full names need every ancestor in place
+        let full_names: Vec<(String, String)> = index
+            .tags
+            .keys()
+            .map(|slug| {
+                let names: Vec<&str> = ancestors_and_self(slug)
+                    .into_iter()
+                    .filter_map(|s| index.tags.get(s).map(|t| t.name.as_str()))
+                    .collect();
+                (slug.clone(), names.join(" / "))
+            })
+            .collect();
+        for (slug, full_name) in full_names {
+            if let Some(tag) = index.tags.get_mut(&slug) {
+                tag.full_name = full_name;
+            }
+        }
+
+        index
+    }
+
+    /// Canonical slug for a tag as written in frontmatter or a URL. An alias
+    /// of a top-level tag also applies to its children: with `rustlang` an
+    /// alias of `rust`, `rustlang/async` is `rust/async`.
+    pub fn canonical(&self, tag: &str) -> String {
+        let slug = slugify(tag);
+        if let Some(canonical) = self.aliases.get(&slug) {
+            return canonical.clone();
+        }
+        match slug.split_once('/') {
+            Some((first, rest)) => match self.aliases.get(first) {
+                Some(canonical) => format!("{}/{}", canonical, rest),
+                None => slug,
+            },
+            None => slug,
+        }
+    }
+
+    /// Find a tag by any spelling, slug or alias
+    pub fn get(&self, tag: &str) -> Option<&Tag> {
+        self.tags.get(&self.canonical(tag))
+    }
+
+    /// All tags sorted by slug, so children follow their parent
+    pub fn all(&self) -> impl Iterator<Item = &Tag> {
+        self.tags.values()
+    }
+
+    /// Ancestors of a tag from the top level down, excluding the tag itself
+    pub fn ancestors(&self, tag: &Tag) -> Vec<&Tag> {
+        ancestors_and_self(&tag.slug)
+            .into_iter()
+            .filter(|s| *s != tag.slug)
+            .filter_map(|s| self.tags.get(s))
+            .collect()
+    }
+
+    /// Direct children of a tag
+    pub fn children(&self, tag: &Tag) -> Vec<&Tag> {
+        self.tags
+            .values()
+            .filter(|t| t.parent.as_deref() == Some(tag.slug.as_str()))
+            .collect()
+    }
+
+    /// Check if a frontmatter tag is the given tag or one of its descendants
+    pub fn is_within(&self, raw: &str, slug: &str) -> bool {
+        let canonical = self.canonical(raw);
+        canonical == slug
+            || canonical
+                .strip_prefix(slug)
+                .is_some_and(|rest| rest.starts_with('/'))
+    }
+
+    /// Number of distinct tags
+    pub fn len(&self) -> usize {
+        self.tags.len()
+    }
+
+    /// Check if no published post has tags
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty()
+    }
+
+    /// Resolve frontmatter tags to display links, dropping duplicates
+    pub fn links<'a>(&'a self, tags: &[String]) -> Vec<TagLink<'a>> {
+        let mut seen = HashSet::new();
+        tags.iter()
+            .filter_map(|t| self.get(t))
+            .filter(|t| seen.insert(t.slug.as_str()))
+            .map(|t| TagLink {
+                name: &t.full_name,
+                slug: &t.slug,
+            })
+            .collect()
+    }
+}
+
+/// Load `tags.yaml` from the content directory, if present
+pub async fn load_meta(path: &Path) -> Result<HashMap<String, TagMeta>> {
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+
+    let content = fs::read_to_string(path).await?;
+    let meta: Option<HashMap<String, TagMeta>> =
+        serde_yaml::from_str(&content).with_context(|| format!("Failed to parse {:?}", path))?;
+    Ok(meta.unwrap_or_default())
+}
+
+/// Convert a tag to its URL slug: lowercase words joined by `-`, with
+/// Russian, Ukrainian and Belarusian letters transliterated to Latin. Other
+/// letters, such as `é` or `日本`, are kept, so a slug is not always ASCII.
+/// Hierarchy levels separated by `/` are slugified one by one; empty levels
+/// are dropped.
+pub fn slugify(tag: &str) -> String {
+    tag.split('/')
+        .map(slugify_segment)
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Slugs of every level of a tag path, from the top level down
+fn ancestors_and_self(slug: &str) -> Vec<&str> {
+    if slug.is_empty() {
+        return Vec::new();
+    }
+    slug.match_indices('/')
+        .map(|(i, _)| &slug[..i])
+        .chain(std::iter::once(slug))
+        .collect()
+}
+
+fn slugify_segment(tag: &str) -> String {
+    let mut slug = String::with_capacity(tag.len());
+    let mut pending_dash = false;
+
+    for c in tag.trim().chars().flat_map(char::to_lowercase) {
+        let piece = match transliterate(c) {
+            Some(latin) => latin,
+            None if c.is_alphanumeric() => {
+                push_slug_part(&mut slug, &mut pending_dash, c.encode_utf8(&mut [0; 4]));
+                continue;
+            }
+            None => {
+                pending_dash = true;
+                continue;
+            }
+        };
+        push_slug_part(&mut slug, &mut pending_dash, piece);
+    }
+
+    slug
+}
+
+fn push_slug_part(slug: &mut String, pending_dash: &mut bool, part: &str) {
+    if part.is_empty() {
+        return;
+    }
+    if *pending_dash && !slug.is_empty() {
+        slug.push('-');
+    }
+    *pending_dash = false;
+    slug.push_str(part);
+}
+
+/// Latin transliteration of a lowercase Russian, Ukrainian or Belarusian letter
+fn transliterate(c: char) -> Option<&'static str> {
+    let latin = match c {
+        'а' => "a",
+        'б' => "b",
+        'в' => "v",
+        'г' => "g",
+        'д' => "d",
+        'е' | 'ё' | 'э' => "e",
+        'ж' => "zh",
+        'з' => "z",
+        'и' => "i",
+        'й' | 'ы' => "y",
+        'к' => "k",
+        'л' => "l",
+        'м' => "m",
+        'н' => "n",
+        'о' => "o",
+        'п' => "p",
+        'р' => "r",
+        'с' => "s",
+        'т' => "t",
+        'у' => "u",
+        'ф' => "f",
+        'х' => "kh",
+        'ц' => "ts",
+        'ч' => "ch",
+        'ш' => "sh",
+        'щ' => "shch",
+        'ъ' | 'ь' => "",
+        'ю' => "yu",
+        'я' => "ya",
+        'і' => "i",
+        'ї' => "yi",
+        'є' => "ye",
+        'ґ' => "g",
+        'ў' => "u",
+        _ => return None,
+    };
+    Some(latin)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("Rust"), "rust");
+        assert_eq!(
+            slugify("  Functional Programming "),
+            "functional-programming"
+        );
+        assert_eq!(slugify("C++ & C#"), "c-c");
+        assert_eq!(slugify("Компиляторы"), "kompilyatory");
+        assert_eq!(slugify("Объектный подход"), "obektnyy-podkhod");
+        assert_eq!(slugify("Їжак і ґанок"), "yizhak-i-ganok");
+        assert_eq!(slugify("Café Déjà"), "café-déjà");
+        assert_eq!(slugify("!!!"), "");
+        assert_eq!(slugify("Rust/Async IO"), "rust/async-io");
+        assert_eq!(slugify("/jvm//Scala/"), "jvm/scala");
+    }
+
+    #[test]
+    fn test_hierarchy() {
+        let posts = [
+            post("a", &["Rust/Async"]),
+            post("b", &["rust", "rust/async"]),
+            post("c", &["rust/embedded"]),
+        ];
+        let index = TagIndex::build(posts.iter(), &HashMap::new());
+
+        let rust = index.get("rust").unwrap();
+        assert_eq!(rust.count, 3);
+        assert_eq!(rust.name, "Rust");
+        let children: Vec<_> = index
+            .children(rust)
+            .iter()
+            .map(|t| t.slug.as_str())
+            .collect();
+        assert_eq!(children, ["rust/async", "rust/embedded"]);
+
+        let async_tag = index.get("rust/async").unwrap();
+        assert_eq!(async_tag.count, 2);
+        assert_eq!(async_tag.full_name, "Rust / Async");
+        assert_eq!(index.ancestors(async_tag)[0].slug, "rust");
+
+        assert!(index.is_within("Rust/Async", "rust"));
+        assert!(!index.is_within("rustacean", "rust"));
+    }
+
+    #[test]
+    fn test_oldest_spelling_wins() {
+        let mut newer = post("a", &["rust"]);
+        newer.metadata.date = chrono::Utc::now();
+        let mut older = post("b", &["Rust"]);
+        older.metadata.date = newer.metadata.date - chrono::Duration::days(1);
+        let posts = [newer, older];
+
+        let forward = TagIndex::build(posts.iter(), &HashMap::new());
+        let backward = TagIndex::build(posts.iter().rev(), &HashMap::new());
+        assert_eq!(forward.get("rust").unwrap().name, "Rust");
+        assert_eq!(backward.get("rust").unwrap().name, "Rust");
+    }
+
+    fn post(slug: &str, tags: &[&str]) -> Post {
+        let mut post = Post::fixture(slug);
+        post.metadata.tags = tags.iter().map(|t| t.to_string()).collect();
+        post
+    }
+
+    #[test]
+    fn test_aliases_and_counts() {
+        let meta: HashMap<String, TagMeta> =
+            serde_yaml::from_str("rust:\n  name: Rust\n  aliases: [rustlang]\n").unwrap();
+        let posts = [
+            post("a", &["rust", "Web Dev"]),
+            post("b", &["RustLang", "rust"]),
+            post("c", &["web dev"]),
+        ];
+
+        let index = TagIndex::build(posts.iter(), &meta);
+
+        let rust = index.get("rustlang").unwrap();
+        assert_eq!(rust.slug, "rust");
+        assert_eq!(rust.name, "Rust");
+        assert_eq!(rust.count, 2);
+
+        let web = index.get("WEB-DEV").unwrap();
+        assert_eq!(web.name, "Web Dev");
+        assert_eq!(web.count, 2);
+
+        let links = index.links(&posts[1].metadata.tags);
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].slug, "rust");
+
+        assert_eq!(index.canonical("RustLang/Async"), "rust/async");
+        assert_eq!(index.canonical("web-dev/rustlang"), "web-dev/rustlang");
+    }
+}
@@ -0,0 +1,307 @@
+//! Static export of the whole site: `nebula build --out dist/`.
+//!
+//! Every page is rendered by sending a request through the regular router,
+//! so the output is exactly what the server would return. No database or
+//! Redis is needed: the pool is created lazily and never used, and view
+//! counters and Webmentions are left out. Lists are rendered unpaginated because a static
+//! host cannot serve `?page=N`; search and the contact form stay dynamic.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, StatusCode};
+use axum::Router;
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+use tower::ServiceExt;
+use tracing::{info, warn};
+
+use crate::config::Config;
+use crate::content::redirects::Target;
+use crate::content::ContentStore;
+use crate::state::AppState;
+
+/// What an export wrote
+#[derive(Debug, Default)]
+pub struct ExportSummary {
+    pub pages: usize,
+    pub skipped: usize,
+    pub redirects: usize,
+}
+
+/// Render the site into `out_dir` and copy `static/` next to it
+pub async fn build(config: &Config, out_dir: &Path) -> Result<ExportSummary> {
+    let config = Config {
+        redis_url: None,
+        page_size: usize::MAX,
+        page_cache_ttl: 0,
+        webmentions: false,
+        ..config.clone()
+    };
+
+    let pool = PgPoolOptions::new().connect_lazy_with(PgConnectOptions::new());
+    let state = crate::create_state(&config, pool).await?;
+    let paths = {
+        let content = state.content.read().await;
+        let mut paths = site_paths(&content, &config.languages);
+        let sitemap_urls = crate::routes::feeds::sitemap_urls(&state, &content).len();
+        for n in 1..=crate::sitemap::page_count(sitemap_urls) {
+            paths.insert(format!("/sitemaps/{}.xml", n));
+        }
+        paths
+    };
+    let app = crate::router(&config, state.clone());
+
+    fs::create_dir_all(out_dir).with_context(|| format!("Failed to create {:?}", out_dir))?;
+    let mut summary = ExportSummary::default();
+
+    for path in &paths {
+        match render(&app, path).await? {
+            Some(body) => {
+                let file = output_path(out_dir, path);
+                if let Some(parent) = file.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::write(&file, body).with_context(|| format!("Failed to write {:?}", file))?;
+                summary.pages += 1;
+            }
+            None => summary.skipped += 1,
+        }
+    }
+
+    summary.redirects = write_redirects(&state, out_dir).await?;
+
+    // Page bundle assets, without the markdown sources
+    {
+        let content = state.content.read().await;
+        for post in content.posts.values().filter(|p| !p.metadata.draft) {
+            if let Some(bundle) = &post.bundle {
+                let assets = out_dir
+                    .join(post.path.trim_start_matches('/'))
+                    .join("assets");
+                copy_dir(bundle, &assets, |file| {
+                    file.extension().is_none_or(|e| e != "md")
+                })?;
+            }
+        }
+    }
+
+    copy_dir(Path::new("static"), &out_dir.join("static"), |_| true)?;
+    // Fingerprinted names referenced by the rendered pages
+    for (logical, hashed) in crate::assets::manifest().files() {
+        fs::copy(
+            Path::new("static").join(logical),
+            out_dir.join("static").join(hashed),
+        )?;
+    }
+    let favicon = Path::new("static/favicon.ico");
+    if favicon.exists() {
+        fs::copy(favicon, out_dir.join("favicon.ico"))?;
+    }
+
+    info!(
+        "Exported {} pages and {} redirects to {:?} ({} skipped)",
+        summary.pages, summary.redirects, out_dir, summary.skipped
+    );
+    Ok(summary)
+}
+
+/// Body of a successful response; anything else is logged and skipped
+async fn render(app: &Router, path: &str) -> Result<Option<Vec<u8>>> {
+    let request = Request::get(path).body(Body::empty())?;
+    let response = app.clone().oneshot(request).await?;
+
+    if response.status() != StatusCode::OK {
+        warn!("Skipping {}: {}", path, response.status());
+        return Ok(None);
+    }
+
+    let body = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .with_context(|| format!("Failed to read response for {}", path))?;
+    Ok(Some(body.to_vec()))
+}
+
+/// Every public GET path backed by content
+fn site_paths(content: &ContentStore, languages: &[String]) -> BTreeSet<String> {
+    let mut paths: BTreeSet<String> = [
+        "/",
+        "/blog",
+        "/blog/tags",
+        "/blog/archive",
+        "/projects",
+        "/projects/rss.xml",
+        "/rss.xml",
+        "/atom.xml",
+        "/feed.json",
+        "/sitemap.xml",
+        "/robots.txt",
+        "/og/site.png",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect();
+
+    for post in languages.iter().flat_map(|l| content.published_posts_in(l)) {
+        paths.insert(post.path.clone());
+        if !post.revisions.is_empty() {
+            paths.insert(format!("{}/history", post.path));
+        }
+        if post.metadata.cover_image.is_none() {
+            paths.insert(format!(
+                "{}.png",
+                post.path.replacen("/blog/", "/og/blog/", 1)
+            ));
+        }
+    }
+
+    for lang in &languages[1..] {
+        if content.published_posts_in(lang).is_empty() {
+            continue;
+        }
+        paths.insert(format!("/{}/blog", lang));
+        for feed in ["rss.xml", "atom.xml", "feed.json"] {
+            paths.insert(format!("/{}/{}", lang, feed));
+        }
+    }
+    for (i, lang) in languages.iter().enumerate() {
+        let prefix = if i == 0 {
+            String::new()
+        } else {
+            format!("/{}", lang)
+        };
+        for tag in content.all_tags_in(lang) {
+            paths.insert(format!("{}/blog/tag/{}", prefix, tag.slug));
+            paths.insert(format!("{}/blog/tag/{}/rss.xml", prefix, tag.slug));
+        }
+    }
+
+    for year in content.archive() {
+        paths.insert(format!("/blog/{}", year.year));
+        for month in &year.months {
+            paths.insert(format!("/blog/{}/{:02}", year.year, month.month));
+        }
+    }
+
+    paths.extend(
+        content
+            .projects
+            .keys()
+            .map(|slug| format!("/projects/{}", slug)),
+    );
+    paths.extend(
+        content
+            .projects
+            .iter()
+            .filter(|(_, p)| p.metadata.cover_image.is_none())
+            .map(|(slug, _)| format!("/og/projects/{}.png", slug)),
+    );
+    paths.extend(
+        content
+            .authors
+            .keys()
+            .map(|slug| format!("/authors/{}", slug)),
+    );
+
+    if content.resume.is_some() {
+        paths.extend(
+            ["/resume", "/resume/print", "/resume.json", "/resume.vcf"]
+                .into_iter()
+                .map(String::from),
+        );
+    }
+
+    paths.extend(
+        content
+            .pages
+            .values()
+            .filter(|p| !p.metadata.draft)
+            .map(|p| p.metadata.path.clone()),
+    );
+    for collection in &content.collections {
+        paths.insert(collection.path.clone());
+        paths.extend(
+            collection
+                .published_entries()
+                .into_iter()
+                .map(|e| collection.entry_path(&e.metadata.slug)),
+        );
+    }
+
+    paths
+}
+
+/// File for a URL path: paths with an extension are written as-is, anything
+/// else becomes `<path>/index.html`
+fn output_path(out_dir: &Path, path: &str) -> PathBuf {
+    let relative = path.trim_matches('/');
+    let has_extension = relative
+        .rsplit('/')
+        .next()
+        .is_some_and(|segment| segment.contains('.'));
+
+    if has_extension {
+        out_dir.join(relative)
+    } else {
+        out_dir.join(relative).join("index.html")
+    }
+}
+
+/// Redirects in the `_redirects` format understood by Netlify and Cloudflare
+/// Pages; gone paths are left to the host's 404
+async fn write_redirects(state: &AppState, out_dir: &Path) -> Result<usize> {
+    let content = state.content.read().await;
+    let lines: Vec<_> = content
+        .redirects
+        .iter()
+        .filter_map(|(from, target)| match target {
+            Target::Moved(to) => Some(format!("{} {} 301", from, to)),
+            Target::Gone => None,
+        })
+        .collect();
+
+    if !lines.is_empty() {
+        fs::write(out_dir.join("_redirects"), lines.join("\n") + "\n")?;
+    }
+    Ok(lines.len())
+}
+
+/// Copy a directory tree, keeping only files accepted by `include`
+fn copy_dir(from: &Path, to: &Path, include: fn(&Path) -> bool) -> Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from).with_context(|| format!("Failed to read {:?}", from))? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target, include)?;
+        } else if include(&entry.path()) {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_path() {
+        let out = Path::new("dist");
+        assert_eq!(output_path(out, "/"), Path::new("dist/index.html"));
+        assert_eq!(
+            output_path(out, "/blog/hello"),
+            Path::new("dist/blog/hello/index.html")
+        );
+        assert_eq!(
+            output_path(out, "/ru/rss.xml"),
+            Path::new("dist/ru/rss.xml")
+        );
+        assert_eq!(
+            output_path(out, "/blog/tag/rust/async"),
+            Path::new("dist/blog/tag/rust/async/index.html")
+        );
+    }
+}
@@ -0,0 +1,368 @@
+//! Syndication feeds: one feed model written as RSS 2.0, Atom and JSON Feed 1.1.
+
+use chrono::{DateTime, Utc};
+use quick_xml::events::BytesText;
+use quick_xml::Writer;
+use rss::extension::dublincore::DublinCoreExtensionBuilder;
+use rss::{CategoryBuilder, ChannelBuilder, EnclosureBuilder, GuidBuilder, ItemBuilder};
+use serde::Serialize;
+
+/// A person credited in a feed
+#[derive(Debug, Clone)]
+pub struct FeedAuthor {
+    pub name: String,
+    pub email: Option<String>,
+    /// Absolute URL of the author's page
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FeedItem {
+    /// Absolute URL, also used as the item id
+    pub url: String,
+    pub title: String,
+    pub summary: Option<String>,
+    pub content_html: String,
+    pub published: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+    pub authors: Vec<FeedAuthor>,
+    pub categories: Vec<String>,
+    /// Absolute URL of the cover image
+    pub image: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Feed {
+    pub title: String,
+    pub description: String,
+    pub language: String,
+    /// Absolute URL of the HTML page the feed mirrors
+    pub home_url: String,
+    /// Absolute URL prefix of the feed files (`<base>/rss.xml`, ...)
+    pub feed_base: String,
+    /// Site owner, used for items without authors
+    pub author: FeedAuthor,
+    /// Newest first
+    pub items: Vec<FeedItem>,
+}
+
+impl Feed {
+    /// Latest change to any item
+    pub fn updated(&self) -> Option<DateTime<Utc>> {
+        self.items.iter().map(|i| i.updated).max()
+    }
+
+    fn item_authors<'a>(&'a self, item: &'a FeedItem) -> &'a [FeedAuthor] {
+        if item.authors.is_empty() {
+            std::slice::from_ref(&self.author)
+        } else {
+            &item.authors
+        }
+    }
+
+    /// RSS 2.0 at `/rss.xml`
+    pub fn to_rss(&self) -> String {
+        let items: Vec<_> = self
+            .items
+            .iter()
+            .map(|item| {
+                let authors = self.item_authors(item);
+
+                // RSS `author` must be an email; every author's name goes in `dc:creator`.
+                // The owner's email is only used when the owner wrote the item.
+                let author = authors.iter().find_map(|a| {
+                    a.email
+                        .as_ref()
+                        .or(self
+                            .author
+                            .email
+                            .as_ref()
+                            .filter(|_| a.name == self.author.name))
+                        .map(|e| format!("{} ({})", e, a.name))
+                });
+
+                ItemBuilder::default()
+                    .title(Some(item.title.clone()))
+                    .link(Some(item.url.clone()))
+                    .guid(Some(
+                        GuidBuilder::default()
+                            .value(&item.url)
+                            .permalink(true)
+                            .build(),
+                    ))
+                    .author(author)
+                    .dublin_core_ext(Some(
+                        DublinCoreExtensionBuilder::default()
+                            .creators(authors.iter().map(|a| a.name.clone()).collect::<Vec<_>>())
+                            .build(),
+                    ))
+                    .categories(
+                        item.categories
+                            .iter()
+                            .map(|c| CategoryBuilder::default().name(c.clone()).build())
+                            .collect::<Vec<_>>(),
+                    )
+                    .enclosure(item.image.as_ref().map(|url| {
+                        EnclosureBuilder::default()
+                            .url(url.clone())
+                            .mime_type(image_mime_type(url))
+                            .length("0")
+                            .build()
+                    }))
+                    .description(item.summary.clone())
+                    .pub_date(Some(item.published.to_rfc2822()))
+                    .content(Some(item.content_html.clone()))
+                    .build()
+            })
+            .collect();
+
+        ChannelBuilder::default()
+            .title(&self.title)
+            .link(&self.home_url)
+            .description(&self.description)
+            .language(Some(self.language.clone()))
+            .last_build_date(self.updated().map(|d| d.to_rfc2822()))
+            .items(items)
+            .build()
+            .to_string()
+    }
+
+    /// Atom (RFC 4287) at `/atom.xml`
+    pub fn to_atom(&self) -> String {
+        let mut writer = Writer::new(Vec::new());
+        let updated = self.updated().unwrap_or_else(Utc::now).to_rfc3339();
+
+        let result = writer
+            .create_element("feed")
+            .with_attribute(("xmlns", "http://www.w3.org/2005/Atom"))
+            .with_attribute(("xml:lang", self.language.as_str()))
+            .write_inner_content(|w| -> XmlResult {
+                text_element(w, "title", &self.title)?;
+                text_element(w, "subtitle", &self.description)?;
+                text_element(w, "id", &self.home_url)?;
+                text_element(w, "updated", &updated)?;
+                link(w, "alternate", "text/html", &self.home_url)?;
+                link(
+                    w,
+                    "self",
+                    "application/atom+xml",
+                    &format!("{}/atom.xml", self.feed_base),
+                )?;
+                atom_author(w, &self.author)?;
+
+                for item in &self.items {
+                    w.create_element("entry")
+                        .write_inner_content(|w| -> XmlResult {
+                            text_element(w, "title", &item.title)?;
+                            text_element(w, "id", &item.url)?;
+                            link(w, "alternate", "text/html", &item.url)?;
+                            text_element(w, "published", &item.published.to_rfc3339())?;
+                            text_element(w, "updated", &item.updated.to_rfc3339())?;
+                            for author in self.item_authors(item) {
+                                atom_author(w, author)?;
+                            }
+                            for category in &item.categories {
+                                w.create_element("category")
+                                    .with_attribute(("term", category.as_str()))
+                                    .write_empty()?;
+                            }
+                            if let Some(image) = &item.image {
+                                link(w, "enclosure", image_mime_type(image), image)?;
+                            }
+                            if let Some(summary) = &item.summary {
+                                text_element(w, "summary", summary)?;
+                            }
+                            w.create_element("content")
+                                .with_attribute(("type", "html"))
+                                .write_text_content(BytesText::new(&item.content_html))?;
+                            Ok(())
+                        })?;
+                }
+                Ok(())
+            });
+
+        if let Err(e) = result {
+            tracing::error!("Failed to write Atom feed: {}", e);
+        }
+        let xml = String::from_utf8_lossy(&writer.into_inner()).into_owned();
+        format!(r#"<?xml version="1.0" encoding="utf-8"?>{}"#, xml)
+    }
+
+    /// JSON Feed 1.1 (<https://jsonfeed.org/version/1.1>) at `/feed.json`
+    pub fn to_json(&self) -> String {
+        let json_author = |a: &FeedAuthor| JsonAuthor {
+            name: a.name.clone(),
+            url: a.url.clone(),
+        };
+        let feed = JsonFeed {
+            version: "https://jsonfeed.org/version/1.1",
+            title: &self.title,
+            home_page_url: &self.home_url,
+            feed_url: format!("{}/feed.json", self.feed_base),
+            description: &self.description,
+            language: &self.language,
+            authors: vec![json_author(&self.author)],
+            items: self
+                .items
+                .iter()
+                .map(|item| JsonItem {
+                    id: &item.url,
+                    url: &item.url,
+                    title: &item.title,
+                    summary: item.summary.as_deref(),
+                    content_html: &item.content_html,
+                    image: item.image.as_deref(),
+                    date_published: item.published.to_rfc3339(),
+                    date_modified: item.updated.to_rfc3339(),
+                    authors: self.item_authors(item).iter().map(json_author).collect(),
+                    tags: &item.categories,
+                })
+                .collect(),
+        };
+
+        serde_json::to_string_pretty(&feed).unwrap_or_default()
+    }
+}
+
+#[derive(Serialize)]
+struct JsonFeed<'a> {
+    version: &'static str,
+    title: &'a str,
+    home_page_url: &'a str,
+    feed_url: String,
+    description: &'a str,
+    language: &'a str,
+    authors: Vec<JsonAuthor>,
+    items: Vec<JsonItem<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonAuthor {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+}
+
+#[derive(Serialize)]
+struct JsonItem<'a> {
+    id: &'a str,
+    url: &'a str,
+    title: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<&'a str>,
+    content_html: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    image: Option<&'a str>,
+    date_published: String,
+    date_modified: String,
+    authors: Vec<JsonAuthor>,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    tags: &'a [String],
+}
+
+type XmlResult = Result<(), quick_xml::Error>;
+
+fn text_element(w: &mut Writer<Vec<u8>>, name: &str, text: &str) -> XmlResult {
+    w.create_element(name)
+        .write_text_content(BytesText::new(text))?;
+    Ok(())
+}
+
+fn link(w: &mut Writer<Vec<u8>>, rel: &str, mime_type: &str, href: &str) -> XmlResult {
+    w.create_element("link")
+        .with_attribute(("rel", rel))
+        .with_attribute(("type", mime_type))
+        .with_attribute(("href", href))
+        .write_empty()?;
+    Ok(())
+}
+
+fn atom_author(w: &mut Writer<Vec<u8>>, author: &FeedAuthor) -> XmlResult {
+    w.create_element("author")
+        .write_inner_content(|w| -> XmlResult {
+            text_element(w, "name", &author.name)?;
+            if let Some(url) = &author.url {
+                text_element(w, "uri", url)?;
+            }
+            Ok(())
+        })?;
+    Ok(())
+}
+
+/// Media type of an image by its extension
+fn image_mime_type(url: &str) -> &'static str {
+    let path = url.split(['?', '#']).next().unwrap_or_default();
+    match path
+        .rsplit('.')
+        .next()
+        .map(str::to_ascii_lowercase)
+        .as_deref()
+    {
+        Some("webp") => "image/webp",
+        Some("png") => "image/png",
+        Some("gif") => "image/gif",
+        Some("svg") => "image/svg+xml",
+        Some("avif") => "image/avif",
+        _ => "image/jpeg",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_formats_share_items() {
+        let date = DateTime::parse_from_rfc3339("2025-03-01T10:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let feed = Feed {
+            title: "Site".into(),
+            description: "Posts".into(),
+            language: "en".into(),
+            home_url: "https://example.com/blog".into(),
+            feed_base: "https://example.com".into(),
+            author: FeedAuthor {
+                name: "Owner".into(),
+                email: Some("owner@example.com".into()),
+                url: None,
+            },
+            items: vec![FeedItem {
+                url: "https://example.com/blog/a".into(),
+                title: "A & B".into(),
+                summary: None,
+                content_html: "<p>Hi</p>".into(),
+                published: date,
+                updated: date,
+                authors: Vec::new(),
+                categories: vec!["Rust".into()],
+                image: Some("https://example.com/cover.webp".into()),
+            }],
+        };
+
+        let rss = feed.to_rss();
+        assert!(rss.contains("<author>owner@example.com (Owner)</author>"));
+        let mut guest_post = feed.clone();
+        guest_post.items[0].authors = vec![FeedAuthor {
+            name: "Guest".into(),
+            email: None,
+            url: None,
+        }];
+        let guest_rss = guest_post.to_rss();
+        assert!(!guest_rss.contains("<author>"));
+        assert!(guest_rss.contains("<dc:creator>Guest</dc:creator>"));
+        assert!(rss.contains(r#"type="image/webp""#));
+        assert!(rss.contains("<category>Rust</category>"));
+
+        let atom = feed.to_atom();
+        assert!(atom.contains("<title>A &amp; B</title>"));
+        assert!(atom.contains(r#"<content type="html">&lt;p&gt;Hi&lt;/p&gt;</content>"#));
+        assert!(atom.contains(r#"<category term="Rust"/>"#));
+        assert!(atom.contains("<author><name>Owner</name></author>"));
+
+        let json: serde_json::Value = serde_json::from_str(&feed.to_json()).unwrap();
+        assert_eq!(json["feed_url"], "https://example.com/feed.json");
+        assert_eq!(json["items"][0]["tags"][0], "Rust");
+        assert_eq!(json["items"][0]["authors"][0]["name"], "Owner");
+    }
+}
@@ -0,0 +1,309 @@
+//! Fluent message catalog for UI strings.
+//!
+//! Messages live in `locales/<lang>/*.ftl` and are loaded once at startup.
+//! The UI locale of a request comes from its URL prefix, then the `lang`
+//! cookie, then `Accept-Language`; missing messages fall back to the default
+//! language and finally to the message id.
+
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Context, Result};
+use axum::async_trait;
+use axum::extract::FromRequestParts;
+use axum::http::header::{ACCEPT_LANGUAGE, COOKIE};
+use axum::http::request::Parts;
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource, FluentValue};
+use tokio::fs;
+use unic_langid::LanguageIdentifier;
+
+use super::LangLink;
+use crate::state::AppState;
+
+/// Cookie holding the visitor's chosen UI language
+pub const LOCALE_COOKIE: &str = "lang";
+
+/// Message bundles for every available UI language
+pub struct Catalog {
+    bundles: HashMap<String, FluentBundle<FluentResource>>,
+    default_locale: String,
+}
+
+impl Catalog {
+    /// Load `<dir>/<lang>/*.ftl` for every language directory
+    pub async fn load(dir: &Path, default_locale: &str) -> Result<Self> {
+        let mut bundles = HashMap::new();
+
+        let mut entries = fs::read_dir(dir)
+            .await
+            .with_context(|| format!("Failed to read locales from {:?}", dir))?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            let Some(code) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            if !path.is_dir() {
+                continue;
+            }
+
+            let langid: LanguageIdentifier = code
+                .parse()
+                .map_err(|e| anyhow!("Invalid locale directory {:?}: {}", path, e))?;
+            let mut bundle = FluentBundle::new_concurrent(vec![langid]);
+            // Output goes into HTML, so skip the Unicode isolation marks around arguments
+            bundle.set_use_isolating(false);
+
+            let mut files = fs::read_dir(&path).await?;
+            while let Some(file) = files.next_entry().await? {
+                let file = file.path();
+                if file.extension().is_none_or(|e| e != "ftl") {
+                    continue;
+                }
+
+                let source = fs::read_to_string(&file).await?;
+                let resource = FluentResource::try_new(source).map_err(|(_, errors)| {
+                    anyhow!("Invalid messages in {:?}: {:?}", file, errors)
+                })?;
+                bundle
+                    .add_resource(resource)
+                    .map_err(|errors| anyhow!("Duplicate messages in {:?}: {:?}", file, errors))?;
+            }
+
+            bundles.insert(code.to_string(), bundle);
+        }
+
+        if !bundles.contains_key(default_locale) {
+            return Err(anyhow!(
+                "No messages for the default language {:?} in {:?}",
+                default_locale,
+                dir
+            ));
+        }
+
+        Ok(Self {
+            bundles,
+            default_locale: default_locale.to_string(),
+        })
+    }
+
+    pub fn has_locale(&self, locale: &str) -> bool {
+        self.bundles.contains_key(locale)
+    }
+
+    /// Available locales, the default first
+    pub fn locales(&self) -> Vec<&str> {
+        let mut locales: Vec<_> = self.bundles.keys().map(String::as_str).collect();
+        locales.sort_by_key(|l| (*l != self.default_locale, *l));
+        locales
+    }
+
+    /// Format a message, falling back to the default locale, then to the id
+    pub fn format(&self, locale: &str, id: &str, args: Option<&FluentArgs>) -> String {
+        for bundle in [
+            self.bundles.get(locale),
+            self.bundles.get(&self.default_locale),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            let Some(pattern) = bundle.get_message(id).and_then(|m| m.value()) else {
+                continue;
+            };
+
+            let mut errors = Vec::new();
+            let text = bundle.format_pattern(pattern, args, &mut errors);
+            if !errors.is_empty() {
+                tracing::warn!("Errors formatting message {:?}: {:?}", id, errors);
+            }
+            return text.into_owned();
+        }
+
+        tracing::warn!("Missing message {:?} for locale {:?}", id, locale);
+        id.to_string()
+    }
+
+    /// Pick the UI locale from the URL language, the `lang` cookie and
+    /// `Accept-Language`, in that order
+    pub fn negotiate(
+        &self,
+        url_lang: Option<&str>,
+        cookie: Option<&str>,
+        accept_language: Option<&str>,
+    ) -> String {
+        url_lang
+            .filter(|l| self.has_locale(l))
+            .or_else(|| cookie.filter(|l| self.has_locale(l)))
+            .or_else(|| {
+                accept_language.and_then(|header| {
+                    parse_accept_language(header)
+                        .into_iter()
+                        .find(|l| self.has_locale(l))
+                })
+            })
+            .unwrap_or(&self.default_locale)
+            .to_string()
+    }
+}
+
+/// Primary language subtags from an `Accept-Language` header, by preference
+fn parse_accept_language(header: &str) -> Vec<&str> {
+    let mut langs: Vec<(&str, f32)> = header
+        .split(',')
+        .filter_map(|part| {
+            let mut pieces = part.trim().split(';');
+            let tag = pieces.next()?.trim();
+            let quality = pieces
+                .find_map(|p| p.trim().strip_prefix("q="))
+                .and_then(|q| q.parse().ok())
+                .unwrap_or(1.0);
+            let primary = tag.split('-').next()?;
+            (!primary.is_empty() && primary != "*" && quality > 0.0).then_some((primary, quality))
+        })
+        .collect();
+
+    // Stable sort keeps header order between equal weights
+    langs.sort_by(|a, b| b.1.total_cmp(&a.1));
+    langs.into_iter().map(|(lang, _)| lang).collect()
+}
+
+/// Read a cookie value from the request headers
+fn cookie<'a>(parts: &'a Parts, name: &str) -> Option<&'a str> {
+    parts
+        .headers
+        .get_all(COOKIE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(';'))
+        .find_map(|pair| {
+            let (key, value) = pair.trim().split_once('=')?;
+            (key == name).then_some(value)
+        })
+}
+
+/// Catalog bound to the UI locale of a request, passed to templates as `t`
+#[derive(Clone)]
+pub struct Translator {
+    catalog: Arc<Catalog>,
+    pub locale: String,
+}
+
+impl Translator {
+    pub fn new(catalog: Arc<Catalog>, locale: &str) -> Self {
+        Self {
+            catalog,
+            locale: locale.to_string(),
+        }
+    }
+
+    /// Message without arguments
+    pub fn get(&self, id: &str) -> String {
+        self.catalog.format(&self.locale, id, None)
+    }
+
+    /// Message with a single argument
+    pub fn arg<'v>(
+        &self,
+        id: &str,
+        name: &'static str,
+        value: impl Into<FluentValue<'v>>,
+    ) -> String {
+        let mut args = FluentArgs::new();
+        args.set(name, value);
+        self.catalog.format(&self.locale, id, Some(&args))
+    }
+
+    /// Message with two arguments
+    pub fn arg2<'v>(
+        &self,
+        id: &str,
+        name: &'static str,
+        value: impl Into<FluentValue<'v>>,
+        name2: &'static str,
+        value2: impl Into<FluentValue<'v>>,
+    ) -> String {
+        let mut args = FluentArgs::new();
+        args.set(name, value);
+        args.set(name2, value2);
+        self.catalog.format(&self.locale, id, Some(&args))
+    }
+
+    /// Links that switch the UI language, for the language menu
+    pub fn locale_links(&self) -> Vec<LangLink> {
+        self.catalog
+            .locales()
+            .into_iter()
+            .map(|code| LangLink::new(code, format!("/lang/{}", code), &self.locale))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl FromRequestParts<AppState> for Translator {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let url_lang = super::url_language(parts, &state.config);
+        let accept_language = parts
+            .headers
+            .get(ACCEPT_LANGUAGE)
+            .and_then(|v| v.to_str().ok());
+
+        let locale = state.catalog.negotiate(
+            url_lang.as_deref(),
+            cookie(parts, LOCALE_COOKIE),
+            accept_language,
+        );
+
+        Ok(Translator::new(state.catalog.clone(), &locale))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn catalog() -> Catalog {
+        let mut bundles = HashMap::new();
+        for (code, source) in [
+            ("en", "hello = Hello, { $name }!\nonly-en = English only"),
+            ("ru", "hello = Привет, { $name }!"),
+        ] {
+            let mut bundle = FluentBundle::new_concurrent(vec![code.parse().unwrap()]);
+            bundle.set_use_isolating(false);
+            bundle
+                .add_resource(FluentResource::try_new(source.to_string()).unwrap())
+                .unwrap();
+            bundles.insert(code.to_string(), bundle);
+        }
+        Catalog {
+            bundles,
+            default_locale: "en".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_format_with_fallback() {
+        let t = Translator::new(Arc::new(catalog()), "ru");
+        assert_eq!(t.arg("hello", "name", "Мир"), "Привет, Мир!");
+        assert_eq!(t.get("only-en"), "English only");
+        assert_eq!(t.get("missing"), "missing");
+    }
+
+    #[test]
+    fn test_negotiate() {
+        let catalog = catalog();
+        assert_eq!(catalog.negotiate(Some("ru"), Some("en"), None), "ru");
+        assert_eq!(catalog.negotiate(None, Some("ru"), Some("en")), "ru");
+        assert_eq!(
+            catalog.negotiate(None, Some("xx"), Some("de-DE, ru;q=0.8, en;q=0.5")),
+            "ru"
+        );
+        assert_eq!(catalog.negotiate(None, None, Some("de")), "en");
+    }
+}
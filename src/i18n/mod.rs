@@ -0,0 +1,160 @@
+//! Content languages and language-prefixed URLs.
+//!
+//! The default language is served from the site root (`/blog/...`); every
+//! other configured language lives under its own prefix (`/ru/blog/...`).
+
+pub mod catalog;
+
+pub use catalog::{Catalog, Translator};
+
+use std::convert::Infallible;
+
+use axum::async_trait;
+use axum::extract::{FromRequestParts, OriginalUri};
+use axum::http::request::Parts;
+
+use crate::config::Config;
+use crate::state::AppState;
+
+/// Language of the current request, taken from the URL prefix
+#[derive(Debug, Clone)]
+pub struct Lang {
+    pub code: String,
+    /// Empty for the default language, `/<code>` otherwise
+    pub prefix: String,
+}
+
+impl Lang {
+    pub fn new(code: &str, default_language: &str) -> Self {
+        let prefix = if code == default_language {
+            String::new()
+        } else {
+            format!("/{}", code)
+        };
+
+        Self {
+            code: code.to_string(),
+            prefix,
+        }
+    }
+
+    /// Prefix a site-relative path with this language's URL prefix
+    pub fn path(&self, path: &str) -> String {
+        format!("{}{}", self.prefix, path)
+    }
+}
+
+#[async_trait]
+impl FromRequestParts<AppState> for Lang {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let default = state.config.default_language();
+        let code = url_language(parts, &state.config);
+
+        Ok(Lang::new(code.as_deref().unwrap_or(default), default))
+    }
+}
+
+/// Configured language named by the first segment of the request path
+fn url_language(parts: &Parts, config: &Config) -> Option<String> {
+    // Nested routers see the path without the prefix, so look at the original URI
+    let path = parts
+        .extensions
+        .get::<OriginalUri>()
+        .map(|uri| uri.path())
+        .unwrap_or_else(|| parts.uri.path());
+
+    path.trim_start_matches('/')
+        .split('/')
+        .next()
+        .filter(|segment| config.languages.iter().any(|l| l == segment))
+        .map(str::to_string)
+}
+
+/// Entry in a language switcher or hreflang list
+pub struct LangLink {
+    pub code: String,
+    pub name: String,
+    pub url: String,
+    pub current: bool,
+}
+
+impl LangLink {
+    pub fn new(code: &str, url: String, current: &str) -> Self {
+        Self {
+            code: code.to_string(),
+            name: language_name(code).to_string(),
+            url,
+            current: code == current,
+        }
+    }
+}
+
+/// Native name of a language, as shown in the language switcher
+pub fn language_name(code: &str) -> &str {
+    match code {
+        "en" => "English",
+        "ru" => "Русский",
+        "de" => "Deutsch",
+        "fr" => "Français",
+        "es" => "Español",
+        _ => code,
+    }
+}
+
+/// Open Graph locale for a language
+pub fn og_locale(code: &str) -> &str {
+    match code {
+        "en" => "en_US",
+        "ru" => "ru_RU",
+        "de" => "de_DE",
+        "fr" => "fr_FR",
+        "es" => "es_ES",
+        _ => code,
+    }
+}
+
+/// Split a language suffix off a file stem: `hello-world.ru` -> (`hello-world`, `ru`).
+/// Only configured languages count, so `notes.md` stays whole.
+pub fn split_lang_suffix<'a>(stem: &'a str, languages: &[String]) -> (&'a str, Option<&'a str>) {
+    match stem.rsplit_once('.') {
+        Some((base, lang)) if languages.iter().any(|l| l == lang) => (base, Some(lang)),
+        _ => (stem, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_lang_suffix() {
+        let languages = ["en".to_string(), "ru".to_string()];
+        assert_eq!(
+            split_lang_suffix("hello-world.ru", &languages),
+            ("hello-world", Some("ru"))
+        );
+        assert_eq!(
+            split_lang_suffix("hello-world", &languages),
+            ("hello-world", None)
+        );
+        assert_eq!(
+            split_lang_suffix("release-1.2", &languages),
+            ("release-1.2", None)
+        );
+        assert_eq!(
+            split_lang_suffix("notes.old", &languages),
+            ("notes.old", None)
+        );
+    }
+
+    #[test]
+    fn test_lang_paths() {
+        assert_eq!(Lang::new("en", "en").path("/blog"), "/blog");
+        assert_eq!(Lang::new("ru", "en").path("/blog"), "/ru/blog");
+    }
+}
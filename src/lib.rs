@@ -1,32 +1,43 @@
 /// Application version from Cargo.toml
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
+pub mod assets;
+pub mod cache;
 pub mod config;
 pub mod content;
 pub mod email;
+pub mod export;
+pub mod feed;
+pub mod i18n;
 pub mod models;
+pub mod og;
+pub mod pagination;
 pub mod routes;
+pub mod sitemap;
 pub mod state;
+pub mod structured_data;
 pub mod turnstile;
 pub mod views;
+pub mod webmention;
+
+use std::path::Path;
 
 use anyhow::Result;
 use axum::{
+    middleware,
     routing::{get, post},
     Router,
 };
 use redis::Client as RedisClient;
-use sqlx::postgres::PgPoolOptions;
-use tower_http::{
-    compression::CompressionLayer,
-    services::{ServeDir, ServeFile},
-    trace::TraceLayer,
-};
+use sqlx::{postgres::PgPoolOptions, PgPool};
+use tower_http::{compression::CompressionLayer, services::ServeFile, trace::TraceLayer};
 
 use crate::{config::Config, state::AppState};
 
 /// Create the main application router
 pub async fn create_app(config: &Config) -> Result<Router> {
+    anyhow::ensure!(!config.database_url.is_empty(), "DATABASE_URL must be set");
+
     // Database connection pool
     let pool = PgPoolOptions::new()
         .max_connections(5)
@@ -36,8 +47,22 @@ pub async fn create_app(config: &Config) -> Result<Router> {
     // Run migrations
     sqlx::migrate!("./migrations").run(&pool).await?;
 
+    let state = create_state(config, pool).await?;
+    Ok(router(config, state))
+}
+
+/// Load content and connect optional services
+pub async fn create_state(config: &Config, pool: PgPool) -> Result<AppState> {
     // Load content from filesystem
-    let content_store = content::ContentStore::load(&config.content_dir).await?;
+    let content_store = content::ContentStore::load(&config.content_dir, &config.languages).await?;
+
+    // Fingerprint static files for long-lived caching
+    let manifest = assets::AssetManifest::build(Path::new("static"))?;
+    tracing::info!("Fingerprinted {} static files", manifest.len());
+    assets::init(manifest);
+
+    // Load UI message catalog
+    let catalog = i18n::Catalog::load(Path::new("locales"), config.default_language()).await?;
 
     // Create email service
     let email_service = email::EmailService::new(config);
@@ -65,41 +90,130 @@ pub async fn create_app(config: &Config) -> Result<Router> {
         None
     };
 
+    // Client for verifying Webmention sources
+    let fetcher = webmention::HttpFetcher::new()?;
+
     // Create shared state
-    let state = AppState::new(pool, content_store, config.clone(), email_service, redis);
+    let state = AppState::new(
+        pool,
+        content_store,
+        config.clone(),
+        email_service,
+        redis,
+        catalog,
+        fetcher,
+    );
+    state.prune_og_cache().await;
+    Ok(state)
+}
 
+/// Build the router over prepared state
+pub fn router(config: &Config, state: AppState) -> Router {
     // Build router
-    let app = Router::new()
+    let mut app = Router::new()
         // Pages
         .route("/", get(routes::pages::index))
         .route("/resume", get(routes::resume::show))
+        .route(
+            "/resume/print",
+            get(routes::resume::print).layer(middleware::from_fn(routes::robots::noindex)),
+        )
+        .route("/resume.json", get(routes::resume::json))
+        .route("/resume.vcf", get(routes::resume::vcard))
+        .route("/lang/:code", get(routes::pages::set_locale))
         .route("/projects", get(routes::projects::list))
+        .route("/projects/rss.xml", get(routes::feeds::projects_rss))
         .route("/projects/:slug", get(routes::projects::show))
+        // Generated Open Graph images
+        .route("/og/site.png", get(routes::og::site))
+        .route("/og/projects/:file", get(routes::og::project))
         // Blog
-        .route("/blog", get(routes::blog::list))
-        .route("/blog/tag/:tag", get(routes::blog::by_tag))
-        .route("/blog/:slug", get(routes::blog::show))
+        .merge(localized_routes())
+        .route("/blog/tags", get(routes::blog::tags))
+        .route("/blog/archive", get(routes::blog::archive))
+        // `:slug` is a year here; the segment is shared with `/blog/:slug/history`
+        .route("/blog/:slug/:month", get(routes::blog::archive_month))
+        // Authors
+        .route("/authors/:slug", get(routes::authors::show))
+        // Views counter, loaded by post and project pages
+        .route("/views/:kind/*key", post(routes::views::record))
+        // Search
+        .route(
+            "/search",
+            get(routes::search::search).layer(middleware::from_fn(routes::robots::noindex)),
+        )
         // Contact
         .route("/contact", get(routes::contact::show))
         .route("/contact", post(routes::contact::submit))
+        // Webmentions, received here and loaded by post pages
+        .route("/webmention", post(routes::webmentions::receive))
+        .route("/webmentions/*key", get(routes::webmentions::list))
         // Feeds
-        .route("/rss.xml", get(routes::feeds::rss))
         .route("/sitemap.xml", get(routes::feeds::sitemap))
-        .route("/robots.txt", get(routes::feeds::robots))
+        .route("/sitemaps/:file", get(routes::feeds::sitemap_part))
+        .route("/robots.txt", get(routes::robots::robots))
         // Health check
         .route("/health", get(routes::health::check))
         .route("/health/cdn", get(routes::health::cdn_check))
         .route("/health/cdn/report", post(routes::health::cdn_report))
         // Admin
         .route("/admin/reload", post(routes::admin::reload_content))
+        .route("/admin/webmentions", get(routes::admin::webmentions))
+        .route(
+            "/admin/webmentions/:id/:action",
+            post(routes::admin::moderate_webmention),
+        )
+        .route(
+            "/preview/blog/:slug",
+            get(routes::blog::preview).layer(middleware::from_fn(routes::robots::noindex)),
+        )
+        .route(
+            "/preview/blog/:slug/assets/*file",
+            get(routes::blog::preview_asset).layer(middleware::from_fn(routes::robots::noindex)),
+        )
+        // Markdown pages and collections are configured in content, so their
+        // URLs are resolved at runtime
+        .fallback(routes::pages::fallback);
+
+    // Other languages get the localized routes under their own prefix
+    for lang in &config.languages[1..] {
+        app = app.nest(&format!("/{}", lang), localized_routes());
+    }
+
+    app
         // Static files
-        .nest_service("/static", ServeDir::new("static"))
+        .route("/static/*path", get(routes::assets::serve))
         .route_service("/favicon.ico", ServeFile::new("static/favicon.ico"))
         // Middleware
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            routes::redirects::redirect,
+        ))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            routes::cache::cache_pages,
+        ))
+        // Inside compression, where rendered bodies have a known size
+        .layer(middleware::from_fn(routes::conditional::conditional_get))
         .layer(CompressionLayer::new())
         .layer(TraceLayer::new_for_http())
         // State
-        .with_state(state);
+        .with_state(state)
+}
 
-    Ok(app)
+/// Routes served once per content language
+fn localized_routes() -> Router<AppState> {
+    Router::new()
+        .route("/blog", get(routes::blog::list))
+        .route("/blog/tag/*tag", get(routes::blog::by_tag))
+        .route("/blog/:slug", get(routes::blog::show))
+        .route(
+            "/blog/:slug/history",
+            get(routes::blog::history).layer(middleware::from_fn(routes::robots::noindex)),
+        )
+        .route("/blog/:slug/assets/*file", get(routes::blog::asset))
+        .route("/rss.xml", get(routes::feeds::rss))
+        .route("/atom.xml", get(routes::feeds::atom))
+        .route("/feed.json", get(routes::feeds::json_feed))
+        .route("/og/blog/:file", get(routes::og::post))
 }
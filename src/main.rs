@@ -1,11 +1,14 @@
 use std::net::SocketAddr;
+use std::path::PathBuf;
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use tokio::net::TcpListener;
 use tracing::info;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-use nebula::{config::Config, create_app};
+use nebula::{config::Config, create_app, export};
+
+const USAGE: &str = "Usage: nebula [build [--out <dir>]]";
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -22,6 +25,22 @@ async fn main() -> Result<()> {
     dotenvy::dotenv().ok();
     let config = Config::from_env()?;
 
+    // `nebula build` renders the site to static files instead of serving it
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        None => {}
+        Some("build") => {
+            let out_dir = match &args[1..] {
+                [] => PathBuf::from("dist"),
+                [flag, dir] if flag == "--out" => PathBuf::from(dir),
+                _ => bail!(USAGE),
+            };
+            export::build(&config, &out_dir).await?;
+            return Ok(());
+        }
+        Some(_) => bail!(USAGE),
+    }
+
     // Create application
     let app = create_app(&config).await?;
 
@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+/// Author profile parsed from frontmatter
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthorMetadata {
+    pub name: String,
+    pub slug: String,
+    /// One-line bio for bylines and meta tags; the markdown body is the full bio
+    pub bio: Option<String>,
+    #[serde(default)]
+    pub avatar: Option<String>,
+    #[serde(default)]
+    pub email: Option<String>,
+    #[serde(default)]
+    pub links: Vec<String>,
+}
+
+/// Complete author profile with rendered bio
+#[derive(Debug, Clone)]
+pub struct Author {
+    pub metadata: AuthorMetadata,
+    pub content_html: String,
+}
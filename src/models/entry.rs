@@ -0,0 +1,82 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{de, Deserialize, Deserializer, Serialize};
+
+/// Metadata of an entry in a configured collection (talks, notes, ...)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntryMetadata {
+    pub title: String,
+    pub slug: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub date: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub updated: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub draft: bool,
+    /// Position when the collection is sorted by weight (lowest first)
+    #[serde(default, deserialize_with = "number_or_string")]
+    pub weight: Option<i64>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub cover_image: Option<String>,
+    /// Collection-specific fields, e.g. a talk's venue or video link
+    #[serde(default)]
+    pub extra: BTreeMap<String, serde_json::Value>,
+}
+
+/// Complete collection entry with content
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub metadata: EntryMetadata,
+    pub content_raw: String,
+    pub content_html: String,
+}
+
+impl Entry {
+    /// `extra` field as display text; lists are joined with commas
+    pub fn extra(&self, key: &str) -> Option<String> {
+        self.metadata.extra.get(key).map(display_value)
+    }
+
+    /// All `extra` fields as display text, sorted by key
+    pub fn extra_fields(&self) -> Vec<(&str, String)> {
+        self.metadata
+            .extra
+            .iter()
+            .map(|(key, value)| (key.as_str(), display_value(value)))
+            .collect()
+    }
+}
+
+/// The simple frontmatter parser reads every scalar as a string
+fn number_or_string<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<i64>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Weight {
+        Number(i64),
+        Text(String),
+    }
+
+    match Option::<Weight>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(Weight::Number(n)) => Ok(Some(n)),
+        Some(Weight::Text(s)) => s.trim().parse().map(Some).map_err(de::Error::custom),
+    }
+}
+
+fn display_value(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Array(items) => items
+            .iter()
+            .map(display_value)
+            .collect::<Vec<_>>()
+            .join(", "),
+        serde_json::Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
@@ -1,5 +1,15 @@
+pub mod author;
+pub mod entry;
+pub mod page;
 pub mod post;
 pub mod project;
+pub mod resume;
+pub mod revision;
 
+pub use author::{Author, AuthorMetadata};
+pub use entry::{Entry, EntryMetadata};
+pub use page::{Page, PageMetadata};
 pub use post::{Post, PostMetadata};
 pub use project::{Project, ProjectMetadata};
+pub use resume::Resume;
+pub use revision::Revision;
@@ -0,0 +1,25 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Standalone page (About, Uses, Now, ...) parsed from frontmatter
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PageMetadata {
+    pub title: String,
+    /// Site-relative URL the page is served at, e.g. `/uses`
+    pub path: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub updated: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub draft: bool,
+    #[serde(default)]
+    pub cover_image: Option<String>,
+}
+
+/// Complete page with rendered content
+#[derive(Debug, Clone)]
+pub struct Page {
+    pub metadata: PageMetadata,
+    pub content_html: String,
+}
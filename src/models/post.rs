@@ -1,6 +1,10 @@
+use std::path::PathBuf;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use super::Revision;
+
 /// Blog post metadata parsed from frontmatter
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PostMetadata {
@@ -13,6 +17,19 @@ pub struct PostMetadata {
     pub draft: bool,
     #[serde(default)]
     pub cover_image: Option<String>,
+    /// Author slugs from `content/authors`; empty means the default author
+    #[serde(default)]
+    pub authors: Vec<String>,
+    /// Language code; defaults to the `slug.<lang>.md` file suffix or the site default
+    #[serde(default)]
+    pub lang: Option<String>,
+    /// Shared by all translations of a post; defaults to the file name
+    /// without its language suffix
+    #[serde(default)]
+    pub translation_key: Option<String>,
+    /// Old slugs or site-relative paths that redirect here
+    #[serde(default)]
+    pub aliases: Vec<String>,
 }
 
 /// Complete blog post with content
@@ -22,12 +39,70 @@ pub struct Post {
     pub content_raw: String,
     pub content_html: String,
     pub reading_time_minutes: u32,
+    /// Resolved language code
+    pub lang: String,
+    /// Resolved translation key
+    pub translation_key: String,
+    /// Site-relative URL, including the language prefix
+    pub path: String,
+    /// Markdown file the post was loaded from
+    pub source: PathBuf,
+    /// Directory of a page bundle (`slug/index.md`), whose other files are
+    /// served as the post's assets
+    pub bundle: Option<PathBuf>,
+    /// `updated` from frontmatter, else the last git commit that changed the file
+    pub updated: Option<DateTime<Utc>>,
+    /// Git commits that touched the file, newest first
+    pub revisions: Vec<Revision>,
 }
 
 impl Post {
+    /// Last change: `updated` if known, else the publish date
+    pub fn last_modified(&self) -> DateTime<Utc> {
+        self.updated.unwrap_or(self.metadata.date)
+    }
+
+    /// Words in the Markdown source
+    pub fn word_count(&self) -> usize {
+        self.content_raw.split_whitespace().count()
+    }
+
     /// Estimate reading time based on word count (200 words per minute)
     pub fn estimate_reading_time(content: &str) -> u32 {
         let word_count = content.split_whitespace().count();
         ((word_count as f64) / 200.0).ceil() as u32
     }
 }
+
+#[cfg(test)]
+impl Post {
+    /// Published English post with the given slug, dated now, for tests
+    pub(crate) fn fixture(slug: &str) -> Self {
+        Self {
+            metadata: PostMetadata {
+                title: slug.to_string(),
+                slug: slug.to_string(),
+                description: None,
+                date: Utc::now(),
+                updated: None,
+                tags: Vec::new(),
+                draft: false,
+                cover_image: None,
+                authors: Vec::new(),
+                lang: None,
+                translation_key: None,
+                aliases: Vec::new(),
+            },
+            content_raw: String::new(),
+            content_html: String::new(),
+            reading_time_minutes: 1,
+            lang: "en".to_string(),
+            translation_key: slug.to_string(),
+            path: format!("/blog/{}", slug),
+            source: format!("blog/{}.md", slug).into(),
+            bundle: None,
+            updated: None,
+            revisions: Vec::new(),
+        }
+    }
+}
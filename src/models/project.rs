@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -16,6 +18,9 @@ pub struct ProjectMetadata {
     pub featured: bool,
     #[serde(default)]
     pub cover_image: Option<String>,
+    /// Old slugs or site-relative paths that redirect here
+    #[serde(default)]
+    pub aliases: Vec<String>,
 }
 
 /// Project development status
@@ -34,4 +39,15 @@ pub struct Project {
     pub metadata: ProjectMetadata,
     pub content_raw: String,
     pub content_html: String,
+    /// Markdown file the project was loaded from
+    pub source: PathBuf,
+    /// `updated` from frontmatter, else the last git commit that changed the file
+    pub updated: Option<DateTime<Utc>>,
+}
+
+impl Project {
+    /// Last change: `updated` if known, else the project date
+    pub fn last_modified(&self) -> DateTime<Utc> {
+        self.updated.unwrap_or(self.metadata.date)
+    }
 }
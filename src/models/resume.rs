@@ -0,0 +1,270 @@
+//! Resume in the JSON Resume schema (<https://jsonresume.org/schema>).
+//!
+//! Only the sections the site renders are typed; any other top-level
+//! section (`languages`, `interests`, `meta`, ...) is kept as-is so
+//! `/resume.json` round-trips the source file.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Resume {
+    pub basics: Basics,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub work: Vec<Work>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub education: Vec<Education>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub certificates: Vec<Certificate>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skills: Vec<Skill>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub projects: Vec<ResumeProject>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Basics {
+    pub name: String,
+    /// Job title shown under the name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub phone: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Markdown
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<Location>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub profiles: Vec<Profile>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Location {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub postal_code: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country_code: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Profile {
+    pub network: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Work {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// `YYYY`, `YYYY-MM` or `YYYY-MM-DD`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<String>,
+    /// Missing for the current position
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub highlights: Vec<String>,
+    /// Technologies used (an extension to the schema, which allows extra fields)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keywords: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Education {
+    pub institution: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub area: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub study_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_date: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub courses: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Certificate {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issuer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Skill {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keywords: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResumeProject {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub highlights: Vec<String>,
+}
+
+impl Resume {
+    /// Contact card (vCard 4.0, RFC 6350) built from `basics`
+    pub fn to_vcard(&self) -> String {
+        let basics = &self.basics;
+        let mut lines = vec![
+            "BEGIN:VCARD".to_string(),
+            "VERSION:4.0".to_string(),
+            format!("FN:{}", vcard_escape(&basics.name)),
+        ];
+
+        // Structured name: family;given;additional;prefixes;suffixes
+        let (given, family) = basics
+            .name
+            .rsplit_once(' ')
+            .unwrap_or((basics.name.as_str(), ""));
+        lines.push(format!(
+            "N:{};{};;;",
+            vcard_escape(family),
+            vcard_escape(given)
+        ));
+
+        if let Some(label) = &basics.label {
+            lines.push(format!("TITLE:{}", vcard_escape(label)));
+        }
+        if let Some(email) = &basics.email {
+            lines.push(format!("EMAIL;TYPE=work:{}", vcard_escape(email)));
+        }
+        if let Some(phone) = &basics.phone {
+            lines.push(format!("TEL;VALUE=uri:tel:{}", phone.replace(' ', "")));
+        }
+        if let Some(url) = &basics.url {
+            lines.push(format!("URL:{}", vcard_escape(url)));
+        }
+        if let Some(image) = &basics.image {
+            lines.push(format!("PHOTO:{}", vcard_escape(image)));
+        }
+        if let Some(location) = &basics.location {
+            // Post office box;extended;street;locality;region;code;country
+            let field = |v: &Option<String>| vcard_escape(v.as_deref().unwrap_or_default());
+            lines.push(format!(
+                "ADR;TYPE=work:;;{};{};{};{};{}",
+                field(&location.address),
+                field(&location.city),
+                field(&location.region),
+                field(&location.postal_code),
+                field(&location.country_code),
+            ));
+        }
+        for profile in &basics.profiles {
+            lines.push(format!(
+                "X-SOCIALPROFILE;TYPE={}:{}",
+                vcard_escape(&profile.network.to_lowercase()),
+                vcard_escape(&profile.url)
+            ));
+        }
+        lines.push("END:VCARD".to_string());
+
+        lines
+            .iter()
+            .map(|line| fold_line(line))
+            .collect::<Vec<_>>()
+            .join("\r\n")
+            + "\r\n"
+    }
+}
+
+/// Escape a vCard text value
+fn vcard_escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(',', "\\,")
+        .replace(';', "\\;")
+        .replace('\n', "\\n")
+}
+
+/// Fold a content line to 75 octets without splitting UTF-8 characters
+fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len());
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            width = 1;
+        }
+        folded.push(c);
+        width += c.len_utf8();
+    }
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vcard() {
+        let resume: Resume = serde_yaml::from_str(
+            "basics:\n  name: Jane Q. Doe\n  label: Engineer; Rust, Scala\n  email: jane@example.com\n  \
+             profiles:\n    - network: GitHub\n      url: https://github.com/jane\n\
+             languages:\n  - language: English\n",
+        )
+        .unwrap();
+
+        let card = resume.to_vcard();
+        assert!(card.starts_with("BEGIN:VCARD\r\nVERSION:4.0\r\nFN:Jane Q. Doe\r\n"));
+        assert!(card.contains("N:Doe;Jane Q.;;;\r\n"));
+        assert!(card.contains("TITLE:Engineer\\; Rust\\, Scala\r\n"));
+        assert!(card.contains("X-SOCIALPROFILE;TYPE=github:https://github.com/jane\r\n"));
+        assert!(card.ends_with("END:VCARD\r\n"));
+
+        // Unknown sections survive the round trip to JSON
+        let json = serde_json::to_value(&resume).unwrap();
+        assert_eq!(json["languages"][0]["language"], "English");
+    }
+
+    #[test]
+    fn test_fold_line() {
+        let line = format!("NOTE:{}", "ж".repeat(60));
+        let folded = fold_line(&line);
+        assert!(folded.split("\r\n").all(|part| part.len() <= 75));
+        assert_eq!(folded.replace("\r\n ", ""), line);
+    }
+}
@@ -0,0 +1,16 @@
+use chrono::{DateTime, Utc};
+
+/// A git commit that touched a content file
+#[derive(Debug, Clone)]
+pub struct Revision {
+    pub hash: String,
+    pub date: DateTime<Utc>,
+    pub author: String,
+    pub subject: String,
+}
+
+impl Revision {
+    pub fn short_hash(&self) -> &str {
+        &self.hash[..self.hash.len().min(7)]
+    }
+}
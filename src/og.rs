@@ -0,0 +1,289 @@
+//! Generated Open Graph images.
+//!
+//! Pages without a cover image share a 1200×630 card rendered from
+//! `templates/og/card.svg` with the bundled DejaVu fonts. PNGs are cached on
+//! disk under `OG_CACHE_DIR`, named by a hash of the SVG, so a changed title
+//! or tag list gets a new file. Serving a card refreshes its modification
+//! time; cards left unserved for [`CACHE_MAX_AGE`] are pruned at startup and
+//! on content reload.
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use anyhow::{anyhow, Context, Result};
+use askama::Template;
+use resvg::{tiny_skia, usvg};
+use sha2::{Digest, Sha256};
+
+use crate::config::Config;
+
+const WIDTH: u32 = 1200;
+const HEIGHT: u32 = 630;
+/// Tags shown on a card
+const MAX_TAGS: usize = 5;
+/// How long a cached card may go unserved before it is pruned
+pub const CACHE_MAX_AGE: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// `(font size, characters per line, lines)` tried in order until the title fits
+const TITLE_LAYOUTS: &[(u32, usize, usize)] = &[(64, 26, 3), (52, 32, 4)];
+
+/// Text of one card
+#[derive(Debug, Clone)]
+pub struct OgCard {
+    pub title: String,
+    /// Small line above the title, e.g. the publish date
+    pub kicker: Option<String>,
+    pub tags: Vec<String>,
+}
+
+#[derive(Template)]
+#[template(path = "og/card.svg", escape = "html")]
+struct CardTemplate<'a> {
+    site_title: &'a str,
+    host: &'a str,
+    kicker: Option<&'a str>,
+    lines: Vec<String>,
+    font_size: u32,
+    line_height: usize,
+    first_line_y: usize,
+    tags: String,
+}
+
+pub struct OgRenderer {
+    fontdb: Arc<usvg::fontdb::Database>,
+    cache_dir: PathBuf,
+    site_title: String,
+    host: String,
+}
+
+impl OgRenderer {
+    pub fn new(config: &Config) -> Self {
+        let mut fontdb = usvg::fontdb::Database::new();
+        fontdb.load_font_data(include_bytes!("../fonts/DejaVuSans.ttf").to_vec());
+        fontdb.load_font_data(include_bytes!("../fonts/DejaVuSans-Bold.ttf").to_vec());
+
+        Self {
+            fontdb: Arc::new(fontdb),
+            cache_dir: PathBuf::from(&config.og_cache_dir),
+            site_title: config.site_title.clone(),
+            host: config
+                .site_url
+                .split_once("://")
+                .map_or(config.site_url.as_str(), |(_, host)| host)
+                .trim_end_matches('/')
+                .to_string(),
+        }
+    }
+
+    /// PNG for a card, from the disk cache when it was rendered before.
+    /// Rasterizing takes tens of milliseconds, so call this off the async
+    /// runtime.
+    pub fn render(&self, card: &OgCard) -> Result<Vec<u8>> {
+        let svg = self.svg(card)?;
+        let hash = hex::encode(Sha256::digest(svg.as_bytes()));
+        let path = self.cache_dir.join(format!("{}.png", &hash[..16]));
+        if let Ok(png) = fs::read(&path) {
+            // Mark the card as in use so pruning keeps it
+            let touched = fs::File::options()
+                .write(true)
+                .open(&path)
+                .and_then(|f| f.set_modified(SystemTime::now()));
+            if let Err(e) = touched {
+                tracing::debug!("Failed to touch Open Graph image {:?}: {}", path, e);
+            }
+            return Ok(png);
+        }
+
+        let png = self.rasterize(&svg)?;
+        if let Err(e) = fs::create_dir_all(&self.cache_dir).and_then(|_| fs::write(&path, &png)) {
+            tracing::warn!("Failed to cache Open Graph image {:?}: {}", path, e);
+        }
+        Ok(png)
+    }
+
+    /// Delete cached cards not served for [`CACHE_MAX_AGE`], such as those of
+    /// retitled or removed posts. Returns how many were deleted.
+    pub fn prune(&self) -> usize {
+        let Ok(entries) = fs::read_dir(&self.cache_dir) else {
+            return 0;
+        };
+        let now = SystemTime::now();
+        entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|e| e == "png"))
+            .filter(|path| {
+                fs::metadata(path)
+                    .and_then(|m| m.modified())
+                    .is_ok_and(|modified| {
+                        now.duration_since(modified)
+                            .is_ok_and(|age| age > CACHE_MAX_AGE)
+                    })
+            })
+            .filter(|path| fs::remove_file(path).is_ok())
+            .count()
+    }
+
+    fn svg(&self, card: &OgCard) -> Result<String> {
+        let (font_size, lines) = layout_title(&card.title);
+        let line_height = font_size as usize * 5 / 4;
+
+        CardTemplate {
+            site_title: &self.site_title,
+            host: &self.host,
+            kicker: card.kicker.as_deref(),
+            lines,
+            font_size,
+            line_height,
+            first_line_y: 190 + font_size as usize,
+            tags: card
+                .tags
+                .iter()
+                .take(MAX_TAGS)
+                .map(|t| format!("#{}", t))
+                .collect::<Vec<_>>()
+                .join(" · "),
+        }
+        .render()
+        .context("Failed to render Open Graph card template")
+    }
+
+    fn rasterize(&self, svg: &str) -> Result<Vec<u8>> {
+        let options = usvg::Options {
+            font_family: "DejaVu Sans".into(),
+            fontdb: self.fontdb.clone(),
+            ..usvg::Options::default()
+        };
+        let tree = usvg::Tree::from_str(svg, &options).context("Invalid Open Graph SVG")?;
+
+        let mut pixmap = tiny_skia::Pixmap::new(WIDTH, HEIGHT)
+            .ok_or_else(|| anyhow!("Failed to allocate Open Graph image"))?;
+        resvg::render(&tree, tiny_skia::Transform::default(), &mut pixmap.as_mut());
+        pixmap
+            .encode_png()
+            .context("Failed to encode Open Graph image")
+    }
+}
+
+/// Largest font size at which the title fits, and its lines; titles too long
+/// for the smallest size are cut off with an ellipsis
+fn layout_title(title: &str) -> (u32, Vec<String>) {
+    let mut layout = (0, Vec::new());
+    for &(font_size, max_chars, max_lines) in TITLE_LAYOUTS {
+        let lines = wrap(title, max_chars);
+        if lines.len() <= max_lines {
+            return (font_size, lines);
+        }
+        layout = (font_size, truncate_lines(lines, max_lines));
+    }
+    layout
+}
+
+/// Greedy word wrap by character count; words longer than a line are split
+fn wrap(text: &str, max_chars: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+
+    for word in text.split_whitespace() {
+        let mut word: Vec<char> = word.chars().collect();
+        while word.len() > max_chars {
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            lines.push(word.drain(..max_chars).collect());
+        }
+        let word: String = word.into_iter().collect();
+
+        let line_len = line.chars().count();
+        if line_len > 0 && line_len + 1 + word.chars().count() > max_chars {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(&word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+fn truncate_lines(mut lines: Vec<String>, max_lines: usize) -> Vec<String> {
+    lines.truncate(max_lines);
+    if let Some(last) = lines.last_mut() {
+        let mut chars: Vec<char> = last.chars().collect();
+        chars.pop();
+        *last = format!("{}…", chars.into_iter().collect::<String>().trim_end());
+    }
+    lines
+}
+
+/// Absolute URL of the generic site card used by list pages
+pub fn site_image(config: &Config) -> String {
+    format!("{}/og/site.png", config.site_url)
+}
+
+/// Absolute URL of a post's card; `prefix` is the language path prefix
+pub fn post_image(config: &Config, prefix: &str, slug: &str) -> String {
+    format!("{}{}/og/blog/{}.png", config.site_url, prefix, slug)
+}
+
+pub fn project_image(config: &Config, slug: &str) -> String {
+    format!("{}/og/projects/{}.png", config.site_url, slug)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout_title() {
+        assert_eq!(
+            wrap("Compiler Ideas That Make Code Generators Better", 26),
+            vec!["Compiler Ideas That Make", "Code Generators Better"]
+        );
+        assert_eq!(wrap("abcdefgh ij", 4), vec!["abcd", "efgh", "ij"]);
+
+        let (size, lines) = layout_title("Short title");
+        assert_eq!((size, lines.len()), (64, 1));
+
+        let long = "word ".repeat(40);
+        let (size, lines) = layout_title(&long);
+        assert_eq!((size, lines.len()), (52, 4));
+        assert!(lines[3].ends_with('…'));
+    }
+
+    #[test]
+    fn test_prune_keeps_recent_cards() {
+        let cache_dir = std::env::temp_dir().join(format!("nebula-og-{}", std::process::id()));
+        fs::create_dir_all(&cache_dir).unwrap();
+        let renderer = OgRenderer {
+            fontdb: Arc::new(usvg::fontdb::Database::new()),
+            cache_dir: cache_dir.clone(),
+            site_title: String::new(),
+            host: String::new(),
+        };
+        fs::write(cache_dir.join("recent.png"), b"png").unwrap();
+        fs::write(cache_dir.join("stale.png"), b"png").unwrap();
+        fs::File::options()
+            .write(true)
+            .open(cache_dir.join("stale.png"))
+            .and_then(|f| {
+                f.set_modified(SystemTime::now() - CACHE_MAX_AGE - Duration::from_secs(60))
+            })
+            .unwrap();
+
+        let pruned = renderer.prune();
+        let left: Vec<_> = fs::read_dir(&cache_dir)
+            .unwrap()
+            .flatten()
+            .map(|e| e.file_name())
+            .collect();
+        fs::remove_dir_all(&cache_dir).unwrap();
+        assert_eq!(pruned, 1);
+        assert_eq!(left, ["recent.png"]);
+    }
+}
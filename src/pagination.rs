@@ -0,0 +1,109 @@
+//! Page-number pagination for list pages.
+//!
+//! Pages are addressed with `?page=N`; the first page is the bare list URL so
+//! existing links and canonical URLs keep working.
+
+use std::ops::Range;
+
+use serde::Deserialize;
+
+/// `?page=N` query parameter
+#[derive(Debug, Default, Deserialize)]
+pub struct PageQuery {
+    pub page: Option<usize>,
+}
+
+/// Position of the current page within a list
+#[derive(Debug, Clone)]
+pub struct Pagination {
+    pub page: usize,
+    pub total_pages: usize,
+    page_size: usize,
+    base_path: String,
+}
+
+impl Pagination {
+    /// Paginate `total_items` items. Returns `None` when the requested page
+    /// does not exist; an empty list still has one (empty) page.
+    pub fn new(
+        base_path: impl Into<String>,
+        total_items: usize,
+        page_size: usize,
+        page: Option<usize>,
+    ) -> Option<Self> {
+        let page_size = page_size.max(1);
+        let total_pages = total_items.div_ceil(page_size).max(1);
+        let page = page.unwrap_or(1);
+
+        if page == 0 || page > total_pages {
+            return None;
+        }
+
+        Some(Self {
+            page,
+            total_pages,
+            page_size,
+            base_path: base_path.into(),
+        })
+    }
+
+    /// Index range of the items shown on the current page
+    pub fn range(&self, total_items: usize) -> Range<usize> {
+        let start = (self.page - 1) * self.page_size;
+        start.min(total_items)..(start + self.page_size).min(total_items)
+    }
+
+    /// Site-relative URL of the given page
+    pub fn url_for(&self, page: usize) -> String {
+        if page <= 1 {
+            self.base_path.clone()
+        } else {
+            format!("{}?page={}", self.base_path, page)
+        }
+    }
+
+    /// Site-relative URL of the current page
+    pub fn current_url(&self) -> String {
+        self.url_for(self.page)
+    }
+
+    pub fn prev_url(&self) -> Option<String> {
+        (self.page > 1).then(|| self.url_for(self.page - 1))
+    }
+
+    pub fn next_url(&self) -> Option<String> {
+        (self.page < self.total_pages).then(|| self.url_for(self.page + 1))
+    }
+
+    pub fn is_paginated(&self) -> bool {
+        self.total_pages > 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pagination() {
+        let p = Pagination::new("/blog", 25, 10, None).unwrap();
+        assert_eq!(p.range(25), 0..10);
+        assert_eq!(p.prev_url(), None);
+        assert_eq!(p.next_url().as_deref(), Some("/blog?page=2"));
+
+        let p = Pagination::new("/blog", 25, 10, Some(3)).unwrap();
+        assert_eq!(p.range(25), 20..25);
+        assert_eq!(p.prev_url().as_deref(), Some("/blog?page=2"));
+        assert_eq!(p.next_url(), None);
+
+        let p = Pagination::new("/blog", 25, 10, Some(2)).unwrap();
+        assert_eq!(p.prev_url().as_deref(), Some("/blog"));
+    }
+
+    #[test]
+    fn test_out_of_range() {
+        assert!(Pagination::new("/blog", 25, 10, Some(0)).is_none());
+        assert!(Pagination::new("/blog", 25, 10, Some(4)).is_none());
+        assert!(Pagination::new("/blog", 0, 10, Some(1)).is_some());
+    }
+}
@@ -1,31 +1,49 @@
 use axum::{
-    extract::{Query, State},
+    extract::{Path, Query, State},
     http::StatusCode,
-    response::IntoResponse,
+    response::{IntoResponse, Json},
 };
 use serde::Deserialize;
 
 use crate::state::AppState;
+use crate::webmention::store::{self, Webmention};
+use crate::webmention::Status;
 
 #[derive(Deserialize)]
-pub struct ReloadQuery {
+pub struct SecretQuery {
     secret: String,
 }
 
-/// Reload content from filesystem
-/// Usage: POST /admin/reload?secret=YOUR_SECRET
-pub async fn reload_content(
-    State(state): State<AppState>,
-    Query(query): Query<ReloadQuery>,
-) -> impl IntoResponse {
+#[derive(Deserialize)]
+pub struct WebmentionsQuery {
+    secret: String,
+    status: Option<Status>,
+}
+
+type AdminError = (StatusCode, &'static str);
+
+/// Check the `secret` query parameter against `ADMIN_SECRET`
+pub(crate) fn authorize(state: &AppState, secret: &str) -> Result<(), AdminError> {
     // Check if admin secret is configured
     let Some(admin_secret) = &state.config.admin_secret else {
-        return (StatusCode::FORBIDDEN, "Admin access not configured");
+        return Err((StatusCode::FORBIDDEN, "Admin access not configured"));
     };
 
     // Validate secret
-    if query.secret != *admin_secret {
-        return (StatusCode::FORBIDDEN, "Invalid secret");
+    if secret != admin_secret {
+        return Err((StatusCode::FORBIDDEN, "Invalid secret"));
+    }
+    Ok(())
+}
+
+/// Reload content from filesystem
+/// Usage: POST /admin/reload?secret=YOUR_SECRET
+pub async fn reload_content(
+    State(state): State<AppState>,
+    Query(query): Query<SecretQuery>,
+) -> impl IntoResponse {
+    if let Err(e) = authorize(&state, &query.secret) {
+        return e;
     }
 
     // Reload content
@@ -43,3 +61,54 @@ pub async fn reload_content(
         }
     }
 }
+
+/// List received Webmentions, newest first
+/// Usage: GET /admin/webmentions?secret=YOUR_SECRET[&status=pending]
+pub async fn webmentions(
+    State(state): State<AppState>,
+    Query(query): Query<WebmentionsQuery>,
+) -> Result<Json<Vec<Webmention>>, AdminError> {
+    authorize(&state, &query.secret)?;
+
+    store::list(&state.pool, query.status)
+        .await
+        .map(Json)
+        .map_err(|e| {
+            tracing::error!("{:#}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to list webmentions",
+            )
+        })
+}
+
+/// Approve or reject a Webmention; only approved ones are shown
+/// Usage: POST /admin/webmentions/ID/approve?secret=YOUR_SECRET (or `/reject`)
+pub async fn moderate_webmention(
+    State(state): State<AppState>,
+    Path((id, action)): Path<(i32, String)>,
+    Query(query): Query<SecretQuery>,
+) -> Result<&'static str, AdminError> {
+    authorize(&state, &query.secret)?;
+
+    let status = match action.as_str() {
+        "approve" => Status::Approved,
+        "reject" => Status::Rejected,
+        _ => return Err((StatusCode::NOT_FOUND, "Unknown action")),
+    };
+
+    match store::set_status(&state.pool, id, status).await {
+        Ok(true) => {
+            tracing::info!("Webmention {} {}", id, status.as_str());
+            Ok("Webmention updated")
+        }
+        Ok(false) => Err((StatusCode::NOT_FOUND, "No such webmention")),
+        Err(e) => {
+            tracing::error!("{:#}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to update webmention",
+            ))
+        }
+    }
+}
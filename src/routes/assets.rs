@@ -0,0 +1,54 @@
+use axum::body::Body;
+use axum::extract::{Path, Request};
+use axum::http::header::CACHE_CONTROL;
+use axum::http::{HeaderValue, StatusCode, Uri};
+use axum::response::{IntoResponse, Response};
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use tower::ServiceExt;
+use tower_http::services::ServeDir;
+
+use crate::assets;
+
+/// Fingerprinted URLs change with the file, so they never need revalidation
+const IMMUTABLE: &str = "public, max-age=31536000, immutable";
+/// Plain URLs may change in place
+const SHORT: &str = "public, max-age=3600";
+
+/// Characters escaped when the decoded file name goes back into a URI
+const PATH_ESCAPE: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'/')
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// Files under `static/`, by logical or fingerprinted name
+pub async fn serve(Path(path): Path<String>, request: Request) -> Response {
+    let (file, cache_control) = match assets::manifest().resolve(&path) {
+        Some(logical) => (logical.to_string(), IMMUTABLE),
+        None => (path, SHORT),
+    };
+
+    // ServeDir guards against path traversal and sets the content type
+    let (mut parts, body) = request.into_parts();
+    parts.uri = match format!("/{}", utf8_percent_encode(&file, PATH_ESCAPE)).parse::<Uri>() {
+        Ok(uri) => uri,
+        Err(_) => return StatusCode::NOT_FOUND.into_response(),
+    };
+
+    let response = match ServeDir::new("static")
+        .oneshot(Request::from_parts(parts, body))
+        .await
+    {
+        Ok(response) => response.map(Body::new),
+        Err(e) => match e {},
+    };
+
+    let mut response = response.into_response();
+    if response.status().is_success() {
+        response
+            .headers_mut()
+            .insert(CACHE_CONTROL, HeaderValue::from_static(cache_control));
+    }
+    response
+}
@@ -0,0 +1,90 @@
+use askama::Template;
+use axum::extract::{Path, Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::Html;
+
+use crate::i18n::Translator;
+use crate::models::Author;
+use crate::pagination::{PageQuery, Pagination};
+use crate::routes::blog::{post_items, PostItem, PostItemsTemplate};
+use crate::state::AppState;
+use crate::VERSION;
+
+#[derive(Template)]
+#[template(path = "authors/show.html")]
+struct AuthorTemplate<'a> {
+    t: &'a Translator,
+    title: &'a str,
+    nav_path: &'a str,
+    version: &'a str,
+    canonical_url: String,
+    og_type: &'a str,
+    og_image: Option<String>,
+    json_ld: Option<String>,
+    site_url: &'a str,
+    author: &'a Author,
+    avatar: Option<String>,
+    posts: Vec<PostItem<'a>>,
+    pagination: Pagination,
+}
+
+/// Author profile with their posts
+pub async fn show(
+    State(state): State<AppState>,
+    t: Translator,
+    Path(slug): Path<String>,
+    Query(query): Query<PageQuery>,
+    headers: HeaderMap,
+) -> Result<Html<String>, StatusCode> {
+    let content = state.content.read().await;
+    let author = content.authors.get(&slug).ok_or(StatusCode::NOT_FOUND)?;
+
+    let authored = content.posts_by_author(&slug, state.config.default_author.as_deref());
+    let pagination = Pagination::new(
+        format!("/authors/{}", slug),
+        authored.len(),
+        state.config.page_size,
+        query.page,
+    )
+    .ok_or(StatusCode::NOT_FOUND)?;
+    let posts = post_items(
+        &state,
+        &content,
+        &authored[pagination.range(authored.len())],
+    )
+    .await;
+
+    let rendered = if super::wants_fragment(&headers) {
+        PostItemsTemplate {
+            t: &t,
+            posts,
+            pagination,
+        }
+        .render()
+    } else {
+        let avatar = author
+            .metadata
+            .avatar
+            .as_ref()
+            .map(|a| state.config.resolve_cover_url(a));
+
+        AuthorTemplate {
+            t: &t,
+            title: &author.metadata.name,
+            nav_path: "/blog",
+            version: VERSION,
+            canonical_url: format!("{}{}", state.config.site_url, pagination.current_url()),
+            og_type: "profile",
+            og_image: avatar.clone(),
+            json_ld: None,
+            site_url: &state.config.site_url,
+            author,
+            avatar,
+            posts,
+            pagination,
+        }
+        .render()
+    };
+
+    Ok(Html(rendered.unwrap_or_else(|e| format!("Error: {}", e))))
+}
@@ -1,91 +1,243 @@
 use askama::Template;
-use axum::extract::{ConnectInfo, Path, State};
+use axum::body::Body;
+use axum::extract::{Path, Query, Request, State};
 use axum::http::{HeaderMap, StatusCode};
-use axum::response::Html;
-use std::net::SocketAddr;
+use axum::response::{Html, IntoResponse, Redirect, Response};
+use axum::Extension;
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use serde::Deserialize;
+use std::path::Component;
+use tower::ServiceExt;
+use tower_http::services::ServeFile;
 
+use super::admin;
+use super::conditional::LastModified;
+use crate::content::archive::{self, ArchiveYear};
+use crate::content::tags::{Tag, TagLink};
+use crate::content::ContentStore;
+use crate::i18n::{Lang, LangLink, Translator};
+use crate::models::Post;
+use crate::og;
+use crate::pagination::{PageQuery, Pagination};
 use crate::state::AppState;
+use crate::structured_data::{Agent, BlogPosting, BreadcrumbList, Graph, Node, Person};
 use crate::views::{self, ContentType, ViewsService};
 use crate::VERSION;
 
 #[derive(Template)]
 #[template(path = "blog/list.html")]
 struct BlogListTemplate<'a> {
-    title: &'a str,
+    t: &'a Translator,
+    title: String,
     nav_path: &'a str,
     version: &'a str,
     canonical_url: String,
     og_type: &'a str,
     og_image: Option<String>,
+    json_ld: Option<String>,
+    site_url: &'a str,
+    lang: &'a str,
+    prefix: &'a str,
+    translations: Vec<LangLink>,
     posts: Vec<PostItem<'a>>,
+    pagination: Pagination,
+}
+
+/// Next page of post items for HTMX "load more"
+#[derive(Template)]
+#[template(path = "partials/post-items.html")]
+pub(super) struct PostItemsTemplate<'a> {
+    pub(super) t: &'a Translator,
+    pub(super) posts: Vec<PostItem<'a>>,
+    pub(super) pagination: Pagination,
 }
 
 #[derive(Template)]
 #[template(path = "blog/tag.html")]
 struct BlogTagTemplate<'a> {
+    t: &'a Translator,
     title: String,
-    tag: &'a str,
+    tag: &'a Tag,
+    breadcrumbs: Vec<&'a Tag>,
+    children: Vec<&'a Tag>,
     nav_path: &'a str,
     version: &'a str,
     canonical_url: String,
     og_type: &'a str,
     og_image: Option<String>,
+    json_ld: Option<String>,
+    site_url: &'a str,
+    lang: &'a str,
+    prefix: &'a str,
+    translations: Vec<LangLink>,
     posts: Vec<PostItem<'a>>,
+    pagination: Pagination,
+}
+
+#[derive(Template)]
+#[template(path = "blog/tags.html")]
+struct BlogTagsTemplate<'a> {
+    t: &'a Translator,
+    title: String,
+    nav_path: &'a str,
+    version: &'a str,
+    canonical_url: String,
+    og_type: &'a str,
+    og_image: Option<String>,
+    json_ld: Option<String>,
+    tags: Vec<&'a Tag>,
+}
+
+#[derive(Template)]
+#[template(path = "blog/archive.html")]
+struct BlogArchiveTemplate<'a> {
+    t: &'a Translator,
+    title: String,
+    nav_path: &'a str,
+    version: &'a str,
+    canonical_url: String,
+    og_type: &'a str,
+    og_image: Option<String>,
+    json_ld: Option<String>,
+    breadcrumbs: Vec<(String, String)>,
+    years: Vec<ArchiveYear<'a>>,
 }
 
 #[derive(Template)]
 #[template(path = "blog/post.html")]
 struct BlogPostTemplate<'a> {
+    t: &'a Translator,
     title: &'a str,
     nav_path: &'a str,
     version: &'a str,
     canonical_url: String,
     og_type: &'a str,
     og_image: Option<String>,
+    json_ld: Option<String>,
     description: Option<&'a str>,
     date: String,
-    date_iso: String,
+    /// Last update date, shown when it differs from the publish date
+    updated: Option<String>,
+    /// Set when git history is available for the post
+    history_url: Option<String>,
     reading_time: u32,
-    tags: &'a [String],
+    tags: Vec<TagLink<'a>>,
     content: &'a str,
     cover_image: Option<String>,
-    views_count: Option<String>,
+    /// HTMX endpoint that records a view and returns the counter
+    views_url: Option<String>,
+    webmentions_url: Option<String>,
     site_url: &'a str,
-    author_name: &'a str,
+    authors: Vec<AuthorLink<'a>>,
+    lang: &'a str,
+    prefix: &'a str,
+    translations: Vec<LangLink>,
+    /// Draft previews must not be indexed
+    noindex: bool,
 }
 
-struct PostItem<'a> {
-    title: &'a str,
-    slug: &'a str,
-    description: Option<&'a str>,
+#[derive(Template)]
+#[template(path = "blog/history.html")]
+struct BlogHistoryTemplate<'a> {
+    t: &'a Translator,
+    title: String,
+    nav_path: &'a str,
+    version: &'a str,
+    canonical_url: String,
+    og_type: &'a str,
+    og_image: Option<String>,
+    json_ld: Option<String>,
+    post_title: &'a str,
+    post_url: &'a str,
+    revisions: Vec<RevisionItem<'a>>,
+}
+
+struct RevisionItem<'a> {
     date: String,
-    reading_time: u32,
-    tags: &'a [String],
-    cover_image: Option<String>,
-    views_count: Option<String>,
+    hash: &'a str,
+    short_hash: &'a str,
+    author: &'a str,
+    subject: &'a str,
 }
 
-pub async fn list(State(state): State<AppState>) -> Html<String> {
-    let content = state.content.read().await;
-    let published = content.published_posts();
+pub(super) struct PostItem<'a> {
+    pub(super) title: &'a str,
+    pub(super) url: &'a str,
+    /// URL prefix of the post's language, used for its tag links
+    pub(super) prefix: String,
+    pub(super) description: Option<&'a str>,
+    pub(super) date: String,
+    pub(super) reading_time: u32,
+    pub(super) tags: Vec<TagLink<'a>>,
+    pub(super) cover_image: Option<String>,
+    pub(super) views_count: Option<String>,
+    pub(super) authors: Vec<AuthorLink<'a>>,
+}
+
+/// Author byline; `slug` is set when the author has a profile page
+pub(super) struct AuthorLink<'a> {
+    pub(super) name: &'a str,
+    pub(super) slug: Option<&'a str>,
+}
+
+/// Bylines for a post, falling back to the site author when no profile matches
+pub(super) fn author_links<'a>(
+    state: &'a AppState,
+    content: &'a ContentStore,
+    post: &Post,
+) -> Vec<AuthorLink<'a>> {
+    let authors = content.post_authors(post, state.config.default_author.as_deref());
+    if authors.is_empty() {
+        return vec![AuthorLink {
+            name: &state.config.author_name,
+            slug: None,
+        }];
+    }
+
+    authors
+        .into_iter()
+        .map(|a| AuthorLink {
+            name: &a.metadata.name,
+            slug: Some(&a.metadata.slug),
+        })
+        .collect()
+}
+
+/// Links to the same page in every configured language
+fn lang_links(state: &AppState, current: &Lang, path: &str) -> Vec<LangLink> {
+    let default = state.config.default_language();
+    state
+        .config
+        .languages
+        .iter()
+        .map(|code| LangLink::new(code, Lang::new(code, default).path(path), &current.code))
+        .collect()
+}
 
+/// Build list items for a page of posts, with view counts if Redis is available
+pub(super) async fn post_items<'a>(
+    state: &'a AppState,
+    content: &'a ContentStore,
+    posts: &[&'a Post],
+) -> Vec<PostItem<'a>> {
     // Batch fetch view counts if Redis is available
     let view_counts: Vec<Option<String>> = if let Some(ref redis) = state.redis {
         let service = ViewsService::new(redis.clone());
-        let slugs: Vec<&str> = published.iter().map(|p| p.metadata.slug.as_str()).collect();
-        match service.get_counts(ContentType::Post, &slugs).await {
+        let keys: Vec<String> = posts.iter().map(|p| content.key_of(p)).collect();
+        let keys: Vec<&str> = keys.iter().map(String::as_str).collect();
+        match service.get_counts(ContentType::Post, &keys).await {
             Ok(counts) => counts
                 .into_iter()
                 .map(|c| Some(views::format_count(c)))
                 .collect(),
-            Err(_) => vec![None; published.len()],
+            Err(_) => vec![None; posts.len()],
         }
     } else {
-        vec![None; published.len()]
+        vec![None; posts.len()]
     };
 
-    let posts: Vec<_> = published
-        .into_iter()
+    posts
+        .iter()
         .zip(view_counts)
         .map(|(p, views_count)| {
             let cover_image = p
@@ -95,75 +247,206 @@ pub async fn list(State(state): State<AppState>) -> Html<String> {
                 .map(|c| state.config.resolve_cover_url(c));
             PostItem {
                 title: &p.metadata.title,
-                slug: &p.metadata.slug,
+                url: &p.path,
+                prefix: Lang::new(&p.lang, &content.default_language).prefix,
                 description: p.metadata.description.as_deref(),
                 date: p.metadata.date.format("%Y-%m-%d").to_string(),
                 reading_time: p.reading_time_minutes,
-                tags: &p.metadata.tags,
+                tags: content.tag_links(p),
                 cover_image,
                 views_count,
+                authors: author_links(state, content, p),
             }
         })
-        .collect();
+        .collect()
+}
 
-    let template = BlogListTemplate {
-        title: "Blog",
-        nav_path: "/blog",
-        version: VERSION,
-        canonical_url: format!("{}/blog", state.config.site_url),
-        og_type: "website",
-        og_image: None,
-        posts,
-    };
+pub async fn list(
+    State(state): State<AppState>,
+    t: Translator,
+    lang: Lang,
+    Query(query): Query<PageQuery>,
+    headers: HeaderMap,
+) -> Result<Html<String>, StatusCode> {
+    let content = state.content.read().await;
+    let published = content.published_posts_in(&lang.code);
 
-    Html(
-        template
-            .render()
-            .unwrap_or_else(|e| format!("Error: {}", e)),
+    let pagination = Pagination::new(
+        lang.path("/blog"),
+        published.len(),
+        state.config.page_size,
+        query.page,
     )
+    .ok_or(StatusCode::NOT_FOUND)?;
+    let posts = post_items(
+        &state,
+        &content,
+        &published[pagination.range(published.len())],
+    )
+    .await;
+
+    let rendered = if super::wants_fragment(&headers) {
+        PostItemsTemplate {
+            t: &t,
+            posts,
+            pagination,
+        }
+        .render()
+    } else {
+        BlogListTemplate {
+            t: &t,
+            title: t.get("blog-title"),
+            nav_path: "/blog",
+            version: VERSION,
+            canonical_url: format!("{}{}", state.config.site_url, pagination.current_url()),
+            og_type: "website",
+            og_image: Some(og::site_image(&state.config)),
+            json_ld: None,
+            site_url: &state.config.site_url,
+            lang: &lang.code,
+            prefix: &lang.prefix,
+            translations: lang_links(&state, &lang, "/blog"),
+            posts,
+            pagination,
+        }
+        .render()
+    };
+
+    Ok(Html(rendered.unwrap_or_else(|e| format!("Error: {}", e))))
 }
 
 pub async fn show(
     State(state): State<AppState>,
+    t: Translator,
+    lang: Lang,
     Path(slug): Path<String>,
-    headers: HeaderMap,
-    peer: Option<ConnectInfo<SocketAddr>>,
-) -> Result<Html<String>, StatusCode> {
+) -> Result<Response, StatusCode> {
     let content = state.content.read().await;
 
-    let post = content.posts.get(&slug).ok_or(StatusCode::NOT_FOUND)?;
-
+    let Some(post) = content.post(&lang.code, &slug) else {
+        // `/blog/:year` shares the route with posts
+        return match parse_year(&slug) {
+            Some(year) if lang.prefix.is_empty() => {
+                year_archive(&state, &t, &content, year).map(IntoResponse::into_response)
+            }
+            _ => Err(StatusCode::NOT_FOUND),
+        };
+    };
     if post.metadata.draft {
         return Err(StatusCode::NOT_FOUND);
     }
 
-    // Get views count and record view if Redis is available
-    let views_count = if let Some(ref redis) = state.redis {
-        let service = ViewsService::new(redis.clone());
+    Ok(render_post(
+        &state,
+        &t,
+        &lang,
+        &content,
+        post,
+        &post.content_html,
+        false,
+    ))
+}
+
+#[derive(Deserialize)]
+pub struct PreviewQuery {
+    secret: String,
+    /// Content language of the post; defaults to the visitor's locale
+    lang: Option<String>,
+}
 
-        // Get current count first (for display)
-        let count = service
-            .get_count(ContentType::Post, &slug)
-            .await
-            .unwrap_or(0);
-
-        // Record view in background (fire and forget)
-        let ip = views::extract_client_ip(&headers, peer.map(|p| p.0));
-        let ua = views::extract_user_agent(&headers);
-        if let Some(ip) = ip {
-            let service = ViewsService::new(redis.clone());
-            let slug_owned = slug.clone();
-            tokio::spawn(async move {
-                let _ = service
-                    .record_view(ContentType::Post, &slug_owned, &ip, ua.as_deref())
-                    .await;
-            });
+impl PreviewQuery {
+    /// Checks the secret and resolves the post language: `?lang=` if given,
+    /// else the visitor's locale when it is a content language
+    fn authorize(&self, state: &AppState, t: &Translator) -> Result<Lang, StatusCode> {
+        admin::authorize(state, &self.secret).map_err(|(status, _)| status)?;
+
+        let config = &state.config;
+        let is_content_language = |code: &&str| config.languages.iter().any(|l| l == code);
+        let code = match self.lang.as_deref() {
+            Some(code) => Some(code)
+                .filter(is_content_language)
+                .ok_or(StatusCode::NOT_FOUND)?,
+            None => Some(t.locale.as_str())
+                .filter(is_content_language)
+                .unwrap_or(config.default_language()),
+        };
+        Ok(Lang::new(code, config.default_language()))
+    }
+
+    /// Query string for the post's preview URLs, escaped for HTML attributes
+    fn to_html_query(&self, lang: &Lang) -> String {
+        let mut query = format!(
+            "secret={}",
+            utf8_percent_encode(&self.secret, NON_ALPHANUMERIC)
+        );
+        if !lang.prefix.is_empty() {
+            query.push_str(&format!("&amp;lang={}", lang.code));
         }
+        query
+    }
+}
 
-        Some(views::format_count(count))
-    } else {
-        None
-    };
+/// A post, drafts included, as it will look once published. Bundle files
+/// are served from the preview route too, since drafts have no public assets.
+/// Usage: GET /preview/blog/{slug}?secret=YOUR_SECRET[&lang=ru]
+pub async fn preview(
+    State(state): State<AppState>,
+    t: Translator,
+    Path(slug): Path<String>,
+    Query(query): Query<PreviewQuery>,
+) -> Result<Response, StatusCode> {
+    let lang = query.authorize(&state, &t)?;
+    let t = Translator::new(state.catalog.clone(), &lang.code);
+
+    let content = state.content.read().await;
+    let post = content
+        .post(&lang.code, &slug)
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let html = preview_asset_urls(
+        &post.content_html,
+        &format!("{}/assets/", post.path),
+        &format!("/preview/blog/{}/assets/", slug),
+        &query.to_html_query(&lang),
+    );
+    Ok(render_post(&state, &t, &lang, &content, post, &html, true))
+}
+
+/// Point a post's bundle URLs at the preview asset route, adding the query
+/// that authorizes them
+fn preview_asset_urls(html: &str, base: &str, preview_base: &str, query: &str) -> String {
+    let needle = format!("\"{}", base);
+    let mut out = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(start) = rest.find(&needle) {
+        out.push_str(&rest[..=start]);
+        let after = &rest[start + needle.len()..];
+        let file = &after[..after.find('"').unwrap_or(after.len())];
+        out.push_str(preview_base);
+        out.push_str(file);
+        out.push_str(if file.contains('?') { "&amp;" } else { "?" });
+        out.push_str(query);
+        rest = &after[file.len()..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Post page; previews leave out the view counter and webmentions, which
+/// would record or load data for a URL nobody else can see
+fn render_post(
+    state: &AppState,
+    t: &Translator,
+    lang: &Lang,
+    content: &ContentStore,
+    post: &Post,
+    html: &str,
+    preview: bool,
+) -> Response {
+    let key = content.key_of(post);
+
+    // The counter is loaded (and the view recorded) separately, so the
+    // page itself can be cached
+    let views_url = (state.redis.is_some() && !preview).then(|| format!("/views/post/{}", key));
 
     let cover_image = post
         .metadata
@@ -172,22 +455,166 @@ pub async fn show(
         .map(|c| state.config.resolve_cover_url(c));
 
     let template = BlogPostTemplate {
+        t,
         title: &post.metadata.title,
         nav_path: "/blog",
         version: VERSION,
-        canonical_url: format!("{}/blog/{}", state.config.site_url, slug),
+        canonical_url: format!("{}{}", state.config.site_url, post.path),
         og_type: "article",
-        og_image: cover_image.clone(),
+        og_image: cover_image.clone().or_else(|| {
+            Some(og::post_image(
+                &state.config,
+                &lang.prefix,
+                &post.metadata.slug,
+            ))
+        }),
+        json_ld: Some(post_json_ld(state, content, t, lang, post)),
         description: post.metadata.description.as_deref(),
         date: post.metadata.date.format("%Y-%m-%d").to_string(),
-        date_iso: post.metadata.date.to_rfc3339(),
+        updated: post
+            .updated
+            .map(|d| d.format("%Y-%m-%d").to_string())
+            .filter(|d| *d != post.metadata.date.format("%Y-%m-%d").to_string()),
+        history_url: (!post.revisions.is_empty()).then(|| format!("{}/history", post.path)),
         reading_time: post.reading_time_minutes,
-        tags: &post.metadata.tags,
-        content: &post.content_html,
+        tags: content.tag_links(post),
+        content: html,
         cover_image,
-        views_count,
+        views_url,
+        webmentions_url: (state.config.webmentions && !preview)
+            .then(|| format!("/webmentions/{}", key)),
         site_url: &state.config.site_url,
-        author_name: &state.config.author_name,
+        authors: author_links(state, content, post),
+        lang: &post.lang,
+        prefix: &lang.prefix,
+        translations: content
+            .translations(post)
+            .into_iter()
+            .map(|t| LangLink::new(&t.lang, t.path.clone(), &post.lang))
+            .collect(),
+        noindex: preview,
+    };
+
+    (
+        Extension(LastModified(post.last_modified())),
+        Html(
+            template
+                .render()
+                .unwrap_or_else(|e| format!("Error: {}", e)),
+        ),
+    )
+        .into_response()
+}
+
+/// `BlogPosting` and breadcrumbs for a post page
+fn post_json_ld(
+    state: &AppState,
+    content: &ContentStore,
+    t: &Translator,
+    lang: &Lang,
+    post: &Post,
+) -> String {
+    let config = &state.config;
+    let url = format!("{}{}", config.site_url, post.path);
+    let site_author = || Person {
+        name: config.author_name.clone(),
+        url: Some(config.site_url.clone()),
+        ..Person::default()
+    };
+
+    let mut authors: Vec<Agent> = content
+        .post_authors(post, config.default_author.as_deref())
+        .into_iter()
+        .map(|a| {
+            Agent::Person(Person {
+                name: a.metadata.name.clone(),
+                url: Some(format!("{}/authors/{}", config.site_url, a.metadata.slug)),
+                image: a
+                    .metadata
+                    .avatar
+                    .as_deref()
+                    .map(|i| config.absolute_url(&config.resolve_cover_url(i))),
+                ..Person::default()
+            })
+        })
+        .collect();
+    if authors.is_empty() {
+        authors.push(Agent::Person(site_author()));
+    }
+
+    let posting = BlogPosting {
+        headline: post.metadata.title.clone(),
+        description: post.metadata.description.clone(),
+        url: url.clone(),
+        main_entity_of_page: url.clone(),
+        date_published: post.metadata.date,
+        date_modified: post.last_modified(),
+        in_language: post.lang.clone(),
+        author: authors,
+        publisher: Agent::Person(site_author()),
+        image: post
+            .metadata
+            .cover_image
+            .as_deref()
+            .map(|c| config.absolute_url(&config.resolve_cover_url(c))),
+        keywords: content
+            .tag_links(post)
+            .into_iter()
+            .map(|t| t.name.to_string())
+            .collect(),
+        word_count: post.word_count(),
+    };
+    let breadcrumbs = BreadcrumbList::new(vec![
+        (t.get("nav-home"), config.site_url.clone()),
+        (
+            t.get("nav-blog"),
+            format!("{}{}", config.site_url, lang.path("/blog")),
+        ),
+        (post.metadata.title.clone(), url),
+    ]);
+
+    Graph::new(vec![
+        Node::BlogPosting(posting),
+        Node::BreadcrumbList(breadcrumbs),
+    ])
+    .to_json()
+}
+
+/// Git commits that touched a post
+pub async fn history(
+    State(state): State<AppState>,
+    t: Translator,
+    lang: Lang,
+    Path(slug): Path<String>,
+) -> Result<Html<String>, StatusCode> {
+    let content = state.content.read().await;
+    let post = content
+        .post(&lang.code, &slug)
+        .filter(|p| !p.metadata.draft)
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let template = BlogHistoryTemplate {
+        t: &t,
+        title: t.arg("history-title", "title", post.metadata.title.as_str()),
+        nav_path: "/blog",
+        version: VERSION,
+        canonical_url: format!("{}{}/history", state.config.site_url, post.path),
+        og_type: "website",
+        og_image: None,
+        json_ld: None,
+        post_title: &post.metadata.title,
+        post_url: &post.path,
+        revisions: post
+            .revisions
+            .iter()
+            .map(|r| RevisionItem {
+                date: r.date.format("%Y-%m-%d").to_string(),
+                hash: &r.hash,
+                short_hash: r.short_hash(),
+                author: &r.author,
+                subject: &r.subject,
+            })
+            .collect(),
     };
 
     Ok(Html(
@@ -197,66 +624,269 @@ pub async fn show(
     ))
 }
 
+/// File from a post's page bundle: `/blog/:slug/assets/*file`.
+/// The markdown sources themselves are never served.
+pub async fn asset(
+    State(state): State<AppState>,
+    lang: Lang,
+    Path((slug, file)): Path<(String, String)>,
+    request: Request,
+) -> Result<Response, StatusCode> {
+    serve_asset(&state, &lang, &slug, &file, false, request).await
+}
+
+/// File from a post's page bundle, drafts included:
+/// `/preview/blog/:slug/assets/*file?secret=…`
+pub async fn preview_asset(
+    State(state): State<AppState>,
+    t: Translator,
+    Path((slug, file)): Path<(String, String)>,
+    Query(query): Query<PreviewQuery>,
+    request: Request,
+) -> Result<Response, StatusCode> {
+    let lang = query.authorize(&state, &t)?;
+    serve_asset(&state, &lang, &slug, &file, true, request).await
+}
+
+async fn serve_asset(
+    state: &AppState,
+    lang: &Lang,
+    slug: &str,
+    file: &str,
+    drafts: bool,
+    request: Request,
+) -> Result<Response, StatusCode> {
+    let file = std::path::Path::new(file.trim_start_matches('/'));
+    let is_safe = file.components().all(|c| match c {
+        Component::Normal(name) => !name.to_string_lossy().starts_with('.'),
+        _ => false,
+    });
+    if !is_safe || file.extension().is_some_and(|e| e == "md") {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    let path = {
+        let content = state.content.read().await;
+        let post = content
+            .post(&lang.code, slug)
+            .filter(|p| drafts || !p.metadata.draft)
+            .ok_or(StatusCode::NOT_FOUND)?;
+        post.bundle
+            .as_ref()
+            .ok_or(StatusCode::NOT_FOUND)?
+            .join(file)
+    };
+
+    match ServeFile::new(path).oneshot(request).await {
+        Ok(response) => Ok(response.map(Body::new)),
+        Err(e) => match e {},
+    }
+}
+
 pub async fn by_tag(
     State(state): State<AppState>,
+    t: Translator,
+    lang: Lang,
     Path(tag): Path<String>,
-) -> Result<Html<String>, StatusCode> {
+    Query(query): Query<PageQuery>,
+    headers: HeaderMap,
+) -> Result<Response, StatusCode> {
     let content = state.content.read().await;
-    let tagged_posts = content.posts_by_tag(&tag);
+    // Tags are hierarchical, so the feed shares the catch-all route
+    if let Some(tag) = tag.strip_suffix("/rss.xml") {
+        return super::feeds::tag_rss(&state, &content, &lang, tag);
+    }
+    // Tags without posts in this language 404 rather than list nothing
+    let tags = content.tags_in(&lang.code);
+    let tag_info = tags.get(&tag).ok_or(StatusCode::NOT_FOUND)?;
 
-    if tagged_posts.is_empty() {
-        return Err(StatusCode::NOT_FOUND);
+    // Aliases and non-canonical spellings redirect to the canonical tag URL
+    if tag != tag_info.slug {
+        let canonical = lang.path(&format!("/blog/tag/{}", tag_info.slug));
+        return Ok(Redirect::permanent(&canonical).into_response());
     }
 
-    // Batch fetch view counts if Redis is available
-    let view_counts: Vec<Option<String>> = if let Some(ref redis) = state.redis {
-        let service = ViewsService::new(redis.clone());
-        let slugs: Vec<&str> = tagged_posts
-            .iter()
-            .map(|p| p.metadata.slug.as_str())
-            .collect();
-        match service.get_counts(ContentType::Post, &slugs).await {
-            Ok(counts) => counts
-                .into_iter()
-                .map(|c| Some(views::format_count(c)))
-                .collect(),
-            Err(_) => vec![None; tagged_posts.len()],
+    let tag_path = format!("/blog/tag/{}", tag_info.slug);
+    let tagged_posts = content.posts_by_tag_in(&lang.code, &tag_info.slug);
+
+    let pagination = Pagination::new(
+        lang.path(&tag_path),
+        tagged_posts.len(),
+        state.config.page_size,
+        query.page,
+    )
+    .ok_or(StatusCode::NOT_FOUND)?;
+    let posts = post_items(
+        &state,
+        &content,
+        &tagged_posts[pagination.range(tagged_posts.len())],
+    )
+    .await;
+
+    let rendered = if super::wants_fragment(&headers) {
+        PostItemsTemplate {
+            t: &t,
+            posts,
+            pagination,
         }
+        .render()
     } else {
-        vec![None; tagged_posts.len()]
+        BlogTagTemplate {
+            t: &t,
+            title: t.arg("tag-title", "tag", tag_info.full_name.as_str()),
+            tag: tag_info,
+            breadcrumbs: tags.ancestors(tag_info),
+            children: tags.children(tag_info),
+            nav_path: "/blog",
+            version: VERSION,
+            canonical_url: format!("{}{}", state.config.site_url, pagination.current_url()),
+            og_type: "website",
+            og_image: Some(og::site_image(&state.config)),
+            json_ld: None,
+            site_url: &state.config.site_url,
+            lang: &lang.code,
+            prefix: &lang.prefix,
+            translations: lang_links(&state, &lang, &tag_path),
+            posts,
+            pagination,
+        }
+        .render()
+    };
+
+    Ok(Html(rendered.unwrap_or_else(|e| format!("Error: {}", e))).into_response())
+}
+
+/// Index of all tags with post counts
+pub async fn tags(State(state): State<AppState>, t: Translator) -> Html<String> {
+    let content = state.content.read().await;
+
+    let mut tags = content.all_tags();
+    tags.sort_by_key(|t| t.full_name.to_lowercase());
+
+    let template = BlogTagsTemplate {
+        t: &t,
+        title: t.get("tags-title"),
+        nav_path: "/blog",
+        version: VERSION,
+        canonical_url: format!("{}/blog/tags", state.config.site_url),
+        og_type: "website",
+        og_image: Some(og::site_image(&state.config)),
+        json_ld: None,
+        tags,
+    };
+
+    Html(
+        template
+            .render()
+            .unwrap_or_else(|e| format!("Error: {}", e)),
+    )
+}
+
+/// Archive of all published posts grouped by year and month
+pub async fn archive(State(state): State<AppState>, t: Translator) -> Html<String> {
+    let content = state.content.read().await;
+
+    let template = BlogArchiveTemplate {
+        t: &t,
+        title: t.get("archive-title"),
+        nav_path: "/blog",
+        version: VERSION,
+        canonical_url: format!("{}/blog/archive", state.config.site_url),
+        og_type: "website",
+        og_image: Some(og::site_image(&state.config)),
+        json_ld: None,
+        breadcrumbs: Vec::new(),
+        years: content.archive(),
     };
 
-    let posts: Vec<_> = tagged_posts
+    Html(
+        template
+            .render()
+            .unwrap_or_else(|e| format!("Error: {}", e)),
+    )
+}
+
+/// Posts published in a single month: `/blog/:year/:month`
+pub async fn archive_month(
+    State(state): State<AppState>,
+    t: Translator,
+    Path((year, month)): Path<(String, String)>,
+) -> Result<Html<String>, StatusCode> {
+    let year = parse_year(&year).ok_or(StatusCode::NOT_FOUND)?;
+    let month: u32 = month
+        .parse()
+        .ok()
+        .filter(|m| (1..=12).contains(m))
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let content = state.content.read().await;
+    let years: Vec<_> = content
+        .archive()
         .into_iter()
-        .zip(view_counts)
-        .map(|(p, views_count)| {
-            let cover_image = p
-                .metadata
-                .cover_image
-                .as_ref()
-                .map(|c| state.config.resolve_cover_url(c));
-            PostItem {
-                title: &p.metadata.title,
-                slug: &p.metadata.slug,
-                description: p.metadata.description.as_deref(),
-                date: p.metadata.date.format("%Y-%m-%d").to_string(),
-                reading_time: p.reading_time_minutes,
-                tags: &p.metadata.tags,
-                cover_image,
-                views_count,
-            }
+        .filter(|y| y.year == year)
+        .map(|mut y| {
+            y.months.retain(|m| m.month == month);
+            y.count = y.months.iter().map(|m| m.count()).sum();
+            y
         })
+        .filter(|y| y.count > 0)
+        .collect();
+
+    if years.is_empty() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    let template = BlogArchiveTemplate {
+        t: &t,
+        title: format!("{} {}", t.get(&archive::month_id(month)), year),
+        nav_path: "/blog",
+        version: VERSION,
+        canonical_url: format!("{}/blog/{}/{:02}", state.config.site_url, year, month),
+        og_type: "website",
+        og_image: Some(og::site_image(&state.config)),
+        json_ld: None,
+        breadcrumbs: vec![
+            (t.get("archive-title"), "/blog/archive".to_string()),
+            (year.to_string(), format!("/blog/{}", year)),
+        ],
+        years,
+    };
+
+    Ok(Html(
+        template
+            .render()
+            .unwrap_or_else(|e| format!("Error: {}", e)),
+    ))
+}
+
+/// Posts published in a single year: `/blog/:year`
+fn year_archive(
+    state: &AppState,
+    t: &Translator,
+    content: &ContentStore,
+    year: i32,
+) -> Result<Html<String>, StatusCode> {
+    let years: Vec<_> = content
+        .archive()
+        .into_iter()
+        .filter(|y| y.year == year)
         .collect();
 
-    let template = BlogTagTemplate {
-        title: format!("Posts tagged \"{}\"", tag),
-        tag: &tag,
+    if years.is_empty() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    let template = BlogArchiveTemplate {
+        t,
+        title: year.to_string(),
         nav_path: "/blog",
         version: VERSION,
-        canonical_url: format!("{}/blog/tag/{}", state.config.site_url, tag),
+        canonical_url: format!("{}/blog/{}", state.config.site_url, year),
         og_type: "website",
-        og_image: None,
-        posts,
+        og_image: Some(og::site_image(&state.config)),
+        json_ld: None,
+        breadcrumbs: vec![(t.get("archive-title"), "/blog/archive".to_string())],
+        years,
     };
 
     Ok(Html(
@@ -265,3 +895,32 @@ pub async fn by_tag(
             .unwrap_or_else(|e| format!("Error: {}", e)),
     ))
 }
+
+/// Parse a four-digit year from a URL segment
+fn parse_year(segment: &str) -> Option<i32> {
+    if segment.len() == 4 && segment.bytes().all(|b| b.is_ascii_digit()) {
+        segment.parse().ok()
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preview_asset_urls() {
+        let html = r#"<img src="/ru/blog/draft/assets/a b.png"><a href="/ru/blog/draft/assets/f.pdf?v=2">f</a><a href="/ru/blog/draft">self</a>"#;
+        let rewritten = preview_asset_urls(
+            html,
+            "/ru/blog/draft/assets/",
+            "/preview/blog/draft/assets/",
+            "secret=s&amp;lang=ru",
+        );
+        assert_eq!(
+            rewritten,
+            r#"<img src="/preview/blog/draft/assets/a b.png?secret=s&amp;lang=ru"><a href="/preview/blog/draft/assets/f.pdf?v=2&amp;secret=s&amp;lang=ru">f</a><a href="/ru/blog/draft">self</a>"#
+        );
+    }
+}
@@ -0,0 +1,127 @@
+use axum::body::{to_bytes, Body, HttpBody};
+use axum::extract::{FromRequestParts, Query, Request, State};
+use axum::http::header::{LAST_MODIFIED, SET_COOKIE, VARY};
+use axum::http::{HeaderName, HeaderValue, Method, StatusCode, Uri};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+
+use crate::i18n::Translator;
+use crate::pagination::PageQuery;
+use crate::state::AppState;
+
+/// Live or per-visitor responses that are never cached
+const UNCACHED_PREFIXES: &[&str] = &[
+    "/static/",
+    "/admin",
+    "/preview/",
+    "/health",
+    "/search",
+    "/views/",
+    "/lang/",
+    "/webmention",
+];
+
+/// Largest rendered body that is cached; bigger or unsized bodies are
+/// streamed uncached
+const MAX_BODY: usize = 8 * 1024 * 1024;
+
+const X_CACHE: HeaderName = HeaderName::from_static("x-cache");
+
+/// Request headers the cache key covers, besides the path
+const VARY_ON: HeaderValue = HeaderValue::from_static("Cookie, Accept-Language, HX-Request");
+
+/// Serve rendered GET responses from the page cache. Keyed by path, `?page=`,
+/// UI locale and whether HTMX asked for a fragment, which is everything the
+/// handlers vary on; other query parameters, such as `utm_*` tags, share an
+/// entry. Responses that set cookies or come from files (with their own
+/// `Last-Modified`) are not cached.
+pub async fn cache_pages(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let path = request.uri().path();
+    let cacheable = state.page_cache.is_enabled()
+        && request.method() == Method::GET
+        && !UNCACHED_PREFIXES.iter().any(|p| path.starts_with(p));
+    if !cacheable {
+        return next.run(request).await;
+    }
+
+    let (mut parts, body) = request.into_parts();
+    let Ok(t) = Translator::from_request_parts(&mut parts, &state).await;
+    let Some(key) = cache_key(&parts.uri, &t.locale, super::wants_fragment(&parts.headers)) else {
+        // A malformed `?page=` is rejected by the handler
+        return next.run(Request::from_parts(parts, body)).await;
+    };
+
+    if let Some(cached) = state.page_cache.get(&key) {
+        let mut response = Response::new(Body::from(cached.body));
+        *response.headers_mut() = cached.headers;
+        *response.extensions_mut() = cached.extensions;
+        response
+            .headers_mut()
+            .insert(X_CACHE, HeaderValue::from_static("HIT"));
+        return response;
+    }
+
+    let generation = state.page_cache.generation();
+    let response = next.run(Request::from_parts(parts, body)).await;
+    if response.status() != StatusCode::OK
+        || response.headers().contains_key(SET_COOKIE)
+        || response.headers().contains_key(LAST_MODIFIED)
+        || response
+            .body()
+            .size_hint()
+            .upper()
+            .is_none_or(|len| len > MAX_BODY as u64)
+    {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    parts.headers.insert(VARY, VARY_ON);
+    let bytes = match to_bytes(body, MAX_BODY).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            tracing::warn!("Failed to buffer response for the page cache: {}", e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+    state.page_cache.insert(
+        generation,
+        key,
+        parts.headers.clone(),
+        parts.extensions.clone(),
+        bytes.clone(),
+    );
+
+    parts
+        .headers
+        .insert(X_CACHE, HeaderValue::from_static("MISS"));
+    Response::from_parts(parts, Body::from(bytes))
+}
+
+/// `None` when the query has a `page` that isn't a number
+fn cache_key(uri: &Uri, locale: &str, fragment: bool) -> Option<String> {
+    let Query(query) = Query::<PageQuery>::try_from_uri(uri).ok()?;
+    let page = query
+        .page
+        .map(|p| format!("?page={}", p))
+        .unwrap_or_default();
+    Some(format!("{}{} {} {}", uri.path(), page, locale, fragment))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_key_keeps_only_page() {
+        let key = |uri: &str| cache_key(&uri.parse().unwrap(), "en", false);
+
+        assert_eq!(key("/blog").as_deref(), Some("/blog en false"));
+        assert_eq!(key("/blog?utm_source=x"), key("/blog"));
+        assert_eq!(
+            key("/blog?utm_source=x&page=2").as_deref(),
+            Some("/blog?page=2 en false")
+        );
+        assert_eq!(key("/blog?page=two"), None);
+    }
+}
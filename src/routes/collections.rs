@@ -0,0 +1,224 @@
+//! Pages of the collections from `content/collections.yaml`. Their URLs are
+//! only known at runtime, so they are served from the router fallback (see
+//! [`super::pages::fallback`]).
+
+use askama::Template;
+use axum::extract::{OriginalUri, Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::Html;
+
+use crate::content::collections::{Collection, ItemLayout, ListLayout};
+use crate::i18n::Translator;
+use crate::models::Entry;
+use crate::og;
+use crate::pagination::{PageQuery, Pagination};
+use crate::state::AppState;
+use crate::structured_data::{BreadcrumbList, Graph, Node};
+use crate::VERSION;
+
+#[derive(Template)]
+#[template(path = "collections/list.html")]
+struct CollectionListTemplate<'a> {
+    t: &'a Translator,
+    title: &'a str,
+    nav_path: &'a str,
+    version: &'a str,
+    canonical_url: String,
+    og_type: &'a str,
+    og_image: Option<String>,
+    json_ld: Option<String>,
+    site_url: &'a str,
+    description: Option<&'a str>,
+    grid: bool,
+    entries: Vec<EntryItem<'a>>,
+    pagination: Pagination,
+}
+
+/// Next page of entries for HTMX "load more"
+#[derive(Template)]
+#[template(path = "partials/entry-items.html")]
+struct EntryItemsTemplate<'a> {
+    t: &'a Translator,
+    grid: bool,
+    entries: Vec<EntryItem<'a>>,
+    pagination: Pagination,
+}
+
+#[derive(Template)]
+#[template(path = "collections/entry.html")]
+struct EntryTemplate<'a> {
+    t: &'a Translator,
+    title: &'a str,
+    nav_path: &'a str,
+    version: &'a str,
+    canonical_url: String,
+    og_type: &'a str,
+    og_image: Option<String>,
+    json_ld: Option<String>,
+    description: Option<&'a str>,
+    /// Plain page layout without date, tags and `extra` fields
+    page: bool,
+    date: Option<String>,
+    tags: &'a [String],
+    extra: Vec<(&'a str, String)>,
+    content: &'a str,
+    cover_image: Option<String>,
+    collection_title: &'a str,
+    collection_path: &'a str,
+}
+
+struct EntryItem<'a> {
+    title: &'a str,
+    url: String,
+    description: Option<&'a str>,
+    date: Option<String>,
+    tags: &'a [String],
+    cover_image: Option<String>,
+    /// `extra` fields, for templates that show e.g. a talk's venue
+    extra: Vec<(&'a str, String)>,
+}
+
+/// Serve a collection list or entry page, or 404 for anything else
+pub async fn show(
+    State(state): State<AppState>,
+    t: Translator,
+    OriginalUri(uri): OriginalUri,
+    Query(query): Query<PageQuery>,
+    headers: HeaderMap,
+) -> Result<Html<String>, StatusCode> {
+    let content = state.content.read().await;
+    let path = uri.path().trim_end_matches('/');
+
+    if let Some(collection) = content.collection_at(path) {
+        return list(&state, &t, collection, query, &headers);
+    }
+
+    let (parent, slug) = path.rsplit_once('/').ok_or(StatusCode::NOT_FOUND)?;
+    let collection = content.collection_at(parent).ok_or(StatusCode::NOT_FOUND)?;
+    let entry = collection
+        .entries
+        .get(slug)
+        .filter(|e| !e.metadata.draft)
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let cover_image = entry
+        .metadata
+        .cover_image
+        .as_ref()
+        .map(|c| state.config.resolve_cover_url(c));
+
+    let canonical_url = format!(
+        "{}{}",
+        state.config.site_url,
+        collection.entry_path(&entry.metadata.slug)
+    );
+    let breadcrumbs = BreadcrumbList::new(vec![
+        (t.get("nav-home"), state.config.site_url.clone()),
+        (
+            collection.title.clone(),
+            format!("{}{}", state.config.site_url, collection.path),
+        ),
+        (entry.metadata.title.clone(), canonical_url.clone()),
+    ]);
+
+    let template = EntryTemplate {
+        t: &t,
+        title: &entry.metadata.title,
+        nav_path: &collection.path,
+        version: VERSION,
+        canonical_url,
+        og_type: "article",
+        og_image: cover_image.clone(),
+        json_ld: Some(Graph::new(vec![Node::BreadcrumbList(breadcrumbs)]).to_json()),
+        description: entry.metadata.description.as_deref(),
+        page: collection.templates.item == ItemLayout::Page,
+        date: entry
+            .metadata
+            .date
+            .map(|d| d.format("%Y-%m-%d").to_string()),
+        tags: &entry.metadata.tags,
+        extra: entry.extra_fields(),
+        content: &entry.content_html,
+        cover_image,
+        collection_title: &collection.title,
+        collection_path: &collection.path,
+    };
+
+    Ok(Html(
+        template
+            .render()
+            .unwrap_or_else(|e| format!("Error: {}", e)),
+    ))
+}
+
+fn list(
+    state: &AppState,
+    t: &Translator,
+    collection: &Collection,
+    query: PageQuery,
+    headers: &HeaderMap,
+) -> Result<Html<String>, StatusCode> {
+    let all_entries = collection.published_entries();
+
+    let pagination = Pagination::new(
+        collection.path.as_str(),
+        all_entries.len(),
+        state.config.page_size,
+        query.page,
+    )
+    .ok_or(StatusCode::NOT_FOUND)?;
+
+    let entries: Vec<_> = all_entries[pagination.range(all_entries.len())]
+        .iter()
+        .map(|e| entry_item(state, collection, e))
+        .collect();
+    let grid = collection.templates.list == ListLayout::Grid;
+
+    let rendered = if super::wants_fragment(headers) {
+        EntryItemsTemplate {
+            t,
+            grid,
+            entries,
+            pagination,
+        }
+        .render()
+    } else {
+        CollectionListTemplate {
+            t,
+            title: &collection.title,
+            nav_path: &collection.path,
+            version: VERSION,
+            canonical_url: format!("{}{}", state.config.site_url, pagination.current_url()),
+            og_type: "website",
+            og_image: Some(og::site_image(&state.config)),
+            json_ld: None,
+            site_url: &state.config.site_url,
+            description: collection.description.as_deref(),
+            grid,
+            entries,
+            pagination,
+        }
+        .render()
+    };
+
+    Ok(Html(rendered.unwrap_or_else(|e| format!("Error: {}", e))))
+}
+
+fn entry_item<'a>(state: &AppState, collection: &Collection, entry: &'a Entry) -> EntryItem<'a> {
+    EntryItem {
+        title: &entry.metadata.title,
+        url: collection.entry_path(&entry.metadata.slug),
+        description: entry.metadata.description.as_deref(),
+        date: entry
+            .metadata
+            .date
+            .map(|d| d.format("%Y-%m-%d").to_string()),
+        tags: &entry.metadata.tags,
+        cover_image: entry
+            .metadata
+            .cover_image
+            .as_ref()
+            .map(|c| state.config.resolve_cover_url(c)),
+        extra: entry.extra_fields(),
+    }
+}
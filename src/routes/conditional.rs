@@ -0,0 +1,139 @@
+use axum::body::{to_bytes, Body, HttpBody};
+use axum::extract::Request;
+use axum::http::header::{CONTENT_LENGTH, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use axum::http::{HeaderMap, HeaderValue, Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+
+/// Response extension set by handlers whose content has a known modification
+/// time; sent as `Last-Modified`
+#[derive(Debug, Clone, Copy)]
+pub struct LastModified(pub DateTime<Utc>);
+
+/// Largest rendered body that gets an ETag; bigger or unsized bodies are
+/// streamed without one
+const MAX_BODY: usize = 8 * 1024 * 1024;
+
+/// Add a weak ETag (and `Last-Modified`, when the handler set one) to
+/// successful GET responses and answer matching conditional requests with
+/// 304. Layered inside compression, where bodies still have a known size; the
+/// ETag is weak because it is hashed before content coding, so gzip and
+/// identity responses share it. Responses that already carry validators, such
+/// as static files, are passed through, and so is HEAD, whose body is empty
+/// by the time it gets here.
+pub async fn conditional_get(request: Request, next: Next) -> Response {
+    if request.method() != Method::GET {
+        return next.run(request).await;
+    }
+    let conditions = request.headers().clone();
+
+    let response = next.run(request).await;
+    if response.status() != StatusCode::OK
+        || response.headers().contains_key(ETAG)
+        || response.headers().contains_key(LAST_MODIFIED)
+        || response
+            .body()
+            .size_hint()
+            .upper()
+            .is_none_or(|len| len > MAX_BODY as u64)
+    {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match to_bytes(body, MAX_BODY).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            tracing::warn!("Failed to buffer response for ETag: {}", e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let etag = format!("\"{}\"", &hex::encode(Sha256::digest(&bytes))[..32]);
+    if let Ok(value) = HeaderValue::from_str(&format!("W/{}", etag)) {
+        parts.headers.insert(ETAG, value);
+    }
+    let last_modified = parts.extensions.get::<LastModified>().map(|m| m.0);
+    if let Some(modified) = last_modified {
+        if let Ok(value) = HeaderValue::from_str(&http_date(modified)) {
+            parts.headers.insert(LAST_MODIFIED, value);
+        }
+    }
+
+    if is_not_modified(&conditions, &etag, last_modified) {
+        parts.status = StatusCode::NOT_MODIFIED;
+        parts.headers.remove(CONTENT_LENGTH);
+        return Response::from_parts(parts, Body::empty());
+    }
+
+    Response::from_parts(parts, Body::from(bytes))
+}
+
+/// `If-None-Match`, compared weakly, wins over `If-Modified-Since` (RFC 9110,
+/// section 13.2.2)
+fn is_not_modified(conditions: &HeaderMap, etag: &str, modified: Option<DateTime<Utc>>) -> bool {
+    if let Some(if_none_match) = conditions.get(IF_NONE_MATCH) {
+        return if_none_match.to_str().is_ok_and(|value| {
+            value
+                .split(',')
+                .map(|tag| tag.trim().trim_start_matches("W/"))
+                .any(|tag| tag == "*" || tag == etag)
+        });
+    }
+
+    let since = conditions
+        .get(IF_MODIFIED_SINCE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| DateTime::parse_from_rfc2822(v).ok());
+    match (modified, since) {
+        // HTTP dates have whole-second precision
+        (Some(modified), Some(since)) => modified.timestamp() <= since.timestamp(),
+        _ => false,
+    }
+}
+
+/// IMF-fixdate, e.g. `Sun, 06 Nov 1994 08:49:37 GMT`
+fn http_date(date: DateTime<Utc>) -> String {
+    date.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_not_modified() {
+        let modified = DateTime::parse_from_rfc3339("2025-03-01T10:00:00.500Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let headers = |name, value: &str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(name, HeaderValue::from_str(value).unwrap());
+            headers
+        };
+
+        assert!(is_not_modified(
+            &headers(IF_NONE_MATCH, "\"a\", W/\"b\""),
+            "\"b\"",
+            None
+        ));
+        assert!(!is_not_modified(
+            &headers(IF_NONE_MATCH, "\"a\""),
+            "\"b\"",
+            Some(modified)
+        ));
+        assert!(is_not_modified(
+            &headers(IF_MODIFIED_SINCE, &http_date(modified)),
+            "\"b\"",
+            Some(modified)
+        ));
+        assert!(!is_not_modified(
+            &headers(IF_MODIFIED_SINCE, "Sat, 01 Mar 2025 09:59:59 GMT"),
+            "\"b\"",
+            Some(modified)
+        ));
+        assert!(!is_not_modified(&HeaderMap::new(), "\"b\"", Some(modified)));
+    }
+}
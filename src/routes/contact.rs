@@ -6,6 +6,7 @@ use axum::{
 };
 use serde::Deserialize;
 
+use crate::i18n::Translator;
 use crate::state::AppState;
 use crate::turnstile;
 use crate::VERSION;
@@ -13,12 +14,14 @@ use crate::VERSION;
 #[derive(Template)]
 #[template(path = "contact.html")]
 struct ContactTemplate<'a> {
+    t: &'a Translator,
     title: &'a str,
     nav_path: &'a str,
     version: &'a str,
     canonical_url: String,
     og_type: &'a str,
     og_image: Option<&'a str>,
+    json_ld: Option<String>,
     author_email: &'a str,
     error: Option<&'a str>,
     form_name: &'a str,
@@ -31,12 +34,14 @@ struct ContactTemplate<'a> {
 #[derive(Template)]
 #[template(path = "contact_success.html")]
 struct ContactSuccessTemplate<'a> {
+    t: &'a Translator,
     title: &'a str,
     nav_path: &'a str,
     version: &'a str,
     canonical_url: String,
     og_type: &'a str,
     og_image: Option<&'a str>,
+    json_ld: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Clone, Default)]
@@ -57,14 +62,16 @@ pub struct ContactFormData {
     pub turnstile_response: String,
 }
 
-pub async fn show(State(state): State<AppState>) -> Html<String> {
+pub async fn show(State(state): State<AppState>, t: Translator) -> Html<String> {
     let template = ContactTemplate {
-        title: "Contact",
+        t: &t,
+        title: &t.get("contact-title"),
         nav_path: "/contact",
         version: VERSION,
         canonical_url: format!("{}/contact", state.config.site_url),
         og_type: "website",
         og_image: None,
+        json_ld: None,
         author_email: &state.config.author_email,
         error: None,
         form_name: "",
@@ -83,6 +90,7 @@ pub async fn show(State(state): State<AppState>) -> Html<String> {
 
 pub async fn submit(
     State(state): State<AppState>,
+    t: Translator,
     Form(form): Form<ContactFormData>,
 ) -> impl IntoResponse {
     // Check honeypot - if filled, it's likely a bot
@@ -90,12 +98,14 @@ pub async fn submit(
         tracing::warn!("Honeypot triggered, rejecting submission");
         // Return success to not reveal the honeypot
         let template = ContactSuccessTemplate {
-            title: "Message Sent",
+            t: &t,
+            title: &t.get("contact-sent-title"),
             nav_path: "/contact",
             version: VERSION,
             canonical_url: format!("{}/contact", state.config.site_url),
             og_type: "website",
             og_image: None,
+            json_ld: None,
         };
         return Html(
             template
@@ -109,42 +119,34 @@ pub async fn submit(
         let secret = state.config.turnstile_secret_key.as_ref().unwrap();
 
         if form.turnstile_response.is_empty() {
-            return render_error(&state, "Please complete the CAPTCHA verification", &form);
+            return render_error(&state, &t, "contact-error-captcha-missing", &form);
         }
 
         match turnstile::verify(secret, &form.turnstile_response, None).await {
             Ok(response) => {
                 if !response.success {
                     tracing::warn!("Turnstile verification failed: {:?}", response.error_codes);
-                    return render_error(
-                        &state,
-                        "CAPTCHA verification failed. Please try again.",
-                        &form,
-                    );
+                    return render_error(&state, &t, "contact-error-captcha-failed", &form);
                 }
             }
             Err(e) => {
                 tracing::error!("Turnstile API error: {}", e);
-                return render_error(
-                    &state,
-                    "CAPTCHA verification error. Please try again.",
-                    &form,
-                );
+                return render_error(&state, &t, "contact-error-captcha-unavailable", &form);
             }
         }
     }
 
     // Validate required fields
     if form.name.trim().is_empty() {
-        return render_error(&state, "Name is required", &form);
+        return render_error(&state, &t, "contact-error-name", &form);
     }
 
     if form.email.trim().is_empty() || !form.email.contains('@') {
-        return render_error(&state, "Valid email is required", &form);
+        return render_error(&state, &t, "contact-error-email", &form);
     }
 
     if form.message.trim().is_empty() {
-        return render_error(&state, "Message is required", &form);
+        return render_error(&state, &t, "contact-error-message", &form);
     }
 
     // Send the email
@@ -161,12 +163,14 @@ pub async fn submit(
     {
         Ok(_) => {
             let template = ContactSuccessTemplate {
-                title: "Message Sent",
+                t: &t,
+                title: &t.get("contact-sent-title"),
                 nav_path: "/contact",
                 version: VERSION,
                 canonical_url: format!("{}/contact", state.config.site_url),
                 og_type: "website",
                 og_image: None,
+                json_ld: None,
             };
             Html(
                 template
@@ -176,25 +180,30 @@ pub async fn submit(
         }
         Err(e) => {
             tracing::error!("Failed to send contact email: {}", e);
-            render_error(
-                &state,
-                "Failed to send message. Please try again later.",
-                &form,
-            )
+            render_error(&state, &t, "contact-error-send", &form)
         }
     }
 }
 
-fn render_error(state: &AppState, error: &str, form: &ContactFormData) -> Html<String> {
+fn render_error(
+    state: &AppState,
+    t: &Translator,
+    error_id: &str,
+    form: &ContactFormData,
+) -> Html<String> {
+    let title = t.get("contact-title");
+    let error = t.get(error_id);
     let template = ContactTemplate {
-        title: "Contact",
+        t,
+        title: &title,
         nav_path: "/contact",
         version: VERSION,
         canonical_url: format!("{}/contact", state.config.site_url),
         og_type: "website",
         og_image: None,
+        json_ld: None,
         author_email: &state.config.author_email,
-        error: Some(error),
+        error: Some(&error),
         form_name: &form.name,
         form_email: &form.email,
         form_subject: &form.subject,
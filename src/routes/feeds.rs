@@ -1,113 +1,433 @@
-use axum::extract::State;
-use axum::http::header;
-use axum::response::{IntoResponse, Response};
-use rss::{ChannelBuilder, GuidBuilder, ItemBuilder};
+use axum::extract::{Path, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Redirect, Response};
+use axum::Extension;
+use chrono::{DateTime, Utc};
 
+use super::conditional::LastModified;
+use crate::content::ContentStore;
+use crate::feed::{Feed, FeedAuthor, FeedItem};
+use crate::i18n::{Lang, Translator};
+use crate::models::{Entry, Post};
+use crate::sitemap::{self, SitemapUrl};
 use crate::state::AppState;
 
-pub async fn robots(State(state): State<AppState>) -> Response {
-    let content = format!(
-        "User-agent: *\n\
-         Allow: /\n\n\
-         Sitemap: {}/sitemap.xml\n",
-        state.config.site_url
+/// Feed item for a published post
+fn post_item(state: &AppState, content: &ContentStore, post: &Post) -> FeedItem {
+    let site_url = &state.config.site_url;
+    FeedItem {
+        url: format!("{}{}", site_url, post.path),
+        title: post.metadata.title.clone(),
+        summary: post.metadata.description.clone(),
+        content_html: post.content_html.clone(),
+        published: post.metadata.date,
+        updated: post.last_modified(),
+        authors: content
+            .post_authors(post, state.config.default_author.as_deref())
+            .into_iter()
+            .map(|a| FeedAuthor {
+                name: a.metadata.name.clone(),
+                email: a.metadata.email.clone(),
+                url: Some(format!("{}/authors/{}", site_url, a.metadata.slug)),
+            })
+            .collect(),
+        categories: content
+            .tag_links(post)
+            .into_iter()
+            .map(|t| t.name.to_string())
+            .collect(),
+        image: post
+            .metadata
+            .cover_image
+            .as_deref()
+            .map(|c| cover_url(state, c)),
+    }
+}
+
+/// Absolute cover image URL; feed readers don't resolve site-relative ones
+fn cover_url(state: &AppState, cover_image: &str) -> String {
+    state
+        .config
+        .absolute_url(&state.config.resolve_cover_url(cover_image))
+}
+
+/// Feed with site-wide metadata; `path` is the HTML page it mirrors
+fn feed(state: &AppState, title: String, language: &str, path: &str, items: Vec<FeedItem>) -> Feed {
+    let site_url = &state.config.site_url;
+    let feed_base = format!("{}{}", site_url, path);
+    Feed {
+        title,
+        description: state.config.site_description.clone(),
+        language: language.to_string(),
+        home_url: feed_base.clone(),
+        feed_base,
+        author: FeedAuthor {
+            name: state.config.author_name.clone(),
+            email: Some(state.config.author_email.clone()),
+            url: Some(site_url.clone()),
+        },
+        items,
+    }
+}
+
+/// Feed of the latest posts in the requested language, shared by all formats
+fn build_feed(state: &AppState, content: &ContentStore, lang: &Lang) -> Feed {
+    let mut items: Vec<FeedItem> = content
+        .published_posts_in(&lang.code)
+        .into_iter()
+        .take(state.config.feed_items)
+        .map(|post| post_item(state, content, post))
+        .collect();
+
+    // Dated entries of collections with `feed: true` (default language only)
+    if lang.code == content.default_language {
+        for collection in content.collections.iter().filter(|c| c.feed) {
+            for entry in collection.published_entries() {
+                let Some(date) = entry.metadata.date else {
+                    continue;
+                };
+                items.push(FeedItem {
+                    url: format!(
+                        "{}{}",
+                        state.config.site_url,
+                        collection.entry_path(&entry.metadata.slug)
+                    ),
+                    title: entry.metadata.title.clone(),
+                    summary: entry.metadata.description.clone(),
+                    content_html: entry.content_html.clone(),
+                    published: date,
+                    updated: entry.metadata.updated.unwrap_or(date),
+                    authors: Vec::new(),
+                    categories: vec![collection.title.clone()],
+                    image: entry
+                        .metadata
+                        .cover_image
+                        .as_deref()
+                        .map(|c| cover_url(state, c)),
+                });
+            }
+        }
+    }
+
+    items.sort_by_key(|item| std::cmp::Reverse(item.published));
+    items.truncate(state.config.feed_items);
+
+    let mut feed = feed(
+        state,
+        state.config.site_title.clone(),
+        &lang.code,
+        &lang.path("/blog"),
+        items,
     );
+    // Site feeds live at the language root, not under /blog
+    feed.feed_base = format!("{}{}", state.config.site_url, lang.prefix);
+    feed
+}
 
+fn feed_response(feed: &Feed, content_type: &'static str, body: String) -> Response {
     (
-        [(header::CONTENT_TYPE, "text/plain; charset=utf-8")],
-        content,
+        feed.updated().map(|m| Extension(LastModified(m))),
+        [(header::CONTENT_TYPE, content_type)],
+        body,
     )
         .into_response()
 }
 
-pub async fn rss(State(state): State<AppState>) -> Response {
+/// RSS 2.0 feed of the latest posts in the requested language
+pub async fn rss(State(state): State<AppState>, lang: Lang) -> Response {
+    let content = state.content.read().await;
+    let feed = build_feed(&state, &content, &lang);
+    feed_response(&feed, "application/rss+xml; charset=utf-8", feed.to_rss())
+}
+
+/// Atom feed of the latest posts in the requested language
+pub async fn atom(State(state): State<AppState>, lang: Lang) -> Response {
+    let content = state.content.read().await;
+    let feed = build_feed(&state, &content, &lang);
+    feed_response(&feed, "application/atom+xml; charset=utf-8", feed.to_atom())
+}
+
+/// JSON Feed 1.1 of the latest posts in the requested language
+pub async fn json_feed(State(state): State<AppState>, lang: Lang) -> Response {
     let content = state.content.read().await;
+    let feed = build_feed(&state, &content, &lang);
+    feed_response(
+        &feed,
+        "application/feed+json; charset=utf-8",
+        feed.to_json(),
+    )
+}
+
+/// RSS feed of the posts with a tag (or its child tags), served at
+/// `/blog/tag/{tag}/rss.xml` through [`super::blog::by_tag`]. The title is in
+/// the feed's language, whatever the visitor's UI language.
+pub fn tag_rss(
+    state: &AppState,
+    content: &ContentStore,
+    lang: &Lang,
+    tag: &str,
+) -> Result<Response, StatusCode> {
+    let t = Translator::new(state.catalog.clone(), &lang.code);
+    let tag_info = content
+        .tags_in(&lang.code)
+        .get(tag)
+        .ok_or(StatusCode::NOT_FOUND)?;
+    if tag != tag_info.slug {
+        let canonical = lang.path(&format!("/blog/tag/{}/rss.xml", tag_info.slug));
+        return Ok(Redirect::permanent(&canonical).into_response());
+    }
 
-    let items: Vec<_> = content
-        .published_posts()
+    let items = content
+        .posts_by_tag_in(&lang.code, &tag_info.slug)
         .into_iter()
-        .take(20)
-        .map(|post| {
-            let post_url = format!("{}/blog/{}", state.config.site_url, post.metadata.slug);
-            let guid = GuidBuilder::default()
-                .value(&post_url)
-                .permalink(true)
-                .build();
-            ItemBuilder::default()
-                .title(Some(post.metadata.title.clone()))
-                .link(Some(post_url))
-                .guid(Some(guid))
-                .author(Some(state.config.author_email.clone()))
-                .description(post.metadata.description.clone())
-                .pub_date(Some(post.metadata.date.to_rfc2822()))
-                .content(Some(post.content_html.clone()))
-                .build()
-        })
+        .take(state.config.feed_items)
+        .map(|post| post_item(state, content, post))
         .collect();
+    let mut feed = feed(
+        state,
+        format!(
+            "{}: {}",
+            state.config.site_title,
+            t.arg("tag-title", "tag", tag_info.full_name.as_str())
+        ),
+        &lang.code,
+        &lang.path(&format!("/blog/tag/{}", tag_info.slug)),
+        items,
+    );
+    if let Some(description) = &tag_info.description {
+        feed.description = description.clone();
+    }
 
-    let channel = ChannelBuilder::default()
-        .title(&state.config.site_title)
-        .link(&state.config.site_url)
-        .description(&state.config.site_description)
-        .items(items)
-        .build();
-
-    (
-        [(header::CONTENT_TYPE, "application/rss+xml; charset=utf-8")],
-        channel.to_string(),
-    )
-        .into_response()
+    Ok(feed_response(
+        &feed,
+        "application/rss+xml; charset=utf-8",
+        feed.to_rss(),
+    ))
 }
 
-pub async fn sitemap(State(state): State<AppState>) -> Response {
+/// RSS feed of projects, newest first. Projects are in the default language,
+/// and so is the title.
+pub async fn projects_rss(State(state): State<AppState>) -> Response {
     let content = state.content.read().await;
+    let t = Translator::new(state.catalog.clone(), &content.default_language);
+    let items = content
+        .all_projects()
+        .into_iter()
+        .take(state.config.feed_items)
+        .map(|project| FeedItem {
+            url: format!(
+                "{}/projects/{}",
+                state.config.site_url, project.metadata.slug
+            ),
+            title: project.metadata.title.clone(),
+            summary: project.metadata.description.clone(),
+            content_html: project.content_html.clone(),
+            published: project.metadata.date,
+            updated: project.last_modified(),
+            authors: Vec::new(),
+            categories: project.metadata.tags.clone(),
+            image: project
+                .metadata
+                .cover_image
+                .as_deref()
+                .map(|c| cover_url(&state, c)),
+        })
+        .collect();
+    let feed = feed(
+        &state,
+        format!("{}: {}", state.config.site_title, t.get("projects-title")),
+        &content.default_language,
+        "/projects",
+        items,
+    );
+
+    feed_response(&feed, "application/rss+xml; charset=utf-8", feed.to_rss())
+}
+
+/// Every URL listed in the sitemap
+pub fn sitemap_urls(state: &AppState, content: &ContentStore) -> Vec<SitemapUrl> {
     let base_url = &state.config.site_url;
+    let url = |path: &str, changefreq| SitemapUrl::new(format!("{}{}", base_url, path), changefreq);
+    let newest_post = |posts: &[&Post]| posts.iter().map(|p| p.last_modified()).max();
+    let cover = |cover_image: &Option<String>| cover_image.as_deref().map(|c| cover_url(state, c));
+    let mut urls = Vec::new();
 
-    let mut xml = String::from(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
-    xml.push_str(r#"<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">"#);
+    // Static pages, dated by their newest item
+    let posts = content.published_posts();
+    let projects = content.all_projects();
+    let newest_project = projects.iter().map(|p| p.last_modified()).max();
+    urls.push(url("", "weekly").lastmod(newest_post(&posts).max(newest_project)));
+    urls.push(url("/blog", "weekly").lastmod(newest_post(&posts)));
+    urls.push(url("/projects", "weekly").lastmod(newest_project));
+    if content.resume.is_some() {
+        urls.push(url("/resume", "weekly"));
+    }
+    urls.push(url("/contact", "weekly"));
 
-    // Static pages
-    for path in &["", "/blog", "/projects", "/resume", "/contact"] {
-        xml.push_str(&format!(
-            "<url><loc>{}{}</loc><changefreq>weekly</changefreq></url>",
-            base_url, path
-        ));
+    // Blog indexes in other languages
+    for lang in &state.config.languages[1..] {
+        let posts = content.published_posts_in(lang);
+        urls.push(url(&format!("/{}/blog", lang), "weekly").lastmod(newest_post(&posts)));
     }
 
-    // Blog posts
-    for post in content.published_posts() {
-        xml.push_str(&format!(
-            "<url><loc>{}/blog/{}</loc><lastmod>{}</lastmod><changefreq>monthly</changefreq></url>",
-            base_url,
-            post.metadata.slug,
-            post.metadata
-                .updated
-                .unwrap_or(post.metadata.date)
-                .format("%Y-%m-%d")
-        ));
+    // Blog posts in every language, each listing its translations
+    for lang in &state.config.languages {
+        for post in content.published_posts_in(lang) {
+            let translations = content.translations(post);
+            let mut entry = url(&post.path, "monthly")
+                .lastmod(Some(post.last_modified()))
+                .image(cover(&post.metadata.cover_image));
+            if translations.len() > 1 {
+                entry.alternates = translations
+                    .iter()
+                    .map(|t| (t.lang.clone(), format!("{}{}", base_url, t.path)))
+                    .collect();
+            }
+            urls.push(entry);
+        }
     }
 
     // Projects
-    for project in content.all_projects() {
-        xml.push_str(&format!(
-            "<url><loc>{}/projects/{}</loc><lastmod>{}</lastmod><changefreq>monthly</changefreq></url>",
-            base_url,
-            project.metadata.slug,
-            project.metadata.updated.unwrap_or(project.metadata.date).format("%Y-%m-%d")
-        ));
+    for project in &projects {
+        urls.push(
+            url(&format!("/projects/{}", project.metadata.slug), "monthly")
+                .lastmod(Some(project.last_modified()))
+                .image(cover(&project.metadata.cover_image)),
+        );
+    }
+
+    // Markdown pages
+    let mut pages: Vec<_> = content
+        .pages
+        .values()
+        .filter(|p| !p.metadata.draft)
+        .collect();
+    pages.sort_by(|a, b| a.metadata.path.cmp(&b.metadata.path));
+    for page in pages {
+        urls.push(
+            url(&page.metadata.path, "monthly")
+                .lastmod(page.metadata.updated)
+                .image(cover(&page.metadata.cover_image)),
+        );
+    }
+
+    // Collections with `sitemap: true`
+    for collection in content.collections.iter().filter(|c| c.sitemap) {
+        let entries = collection.published_entries();
+        let entry_lastmod = |e: &Entry| e.metadata.updated.or(e.metadata.date);
+        urls.push(
+            url(&collection.path, "weekly")
+                .lastmod(entries.iter().filter_map(|e| entry_lastmod(e)).max()),
+        );
+        for entry in entries {
+            urls.push(
+                url(&collection.entry_path(&entry.metadata.slug), "monthly")
+                    .lastmod(entry_lastmod(entry))
+                    .image(cover(&entry.metadata.cover_image)),
+            );
+        }
+    }
+
+    // Archive pages
+    urls.push(url("/blog/archive", "weekly").lastmod(newest_post(&posts)));
+    for year in content.archive() {
+        let year_posts: Vec<_> = year
+            .months
+            .iter()
+            .flat_map(|m| m.posts.iter().copied())
+            .collect();
+        urls.push(
+            url(&format!("/blog/{}", year.year), "monthly").lastmod(newest_post(&year_posts)),
+        );
+        for month in &year.months {
+            urls.push(
+                url(
+                    &format!("/blog/{}/{:02}", month.year, month.month),
+                    "monthly",
+                )
+                .lastmod(newest_post(&month.posts)),
+            );
+        }
     }
 
-    // Tag pages
-    for tag in content.all_tags() {
-        xml.push_str(&format!(
-            "<url><loc>{}/blog/tag/{}</loc><changefreq>weekly</changefreq></url>",
-            base_url, tag
-        ));
+    // Tag pages, listed in each language that has posts with the tag
+    let tags: Vec<_> = content
+        .all_tags()
+        .into_iter()
+        .map(|tag| {
+            url(&format!("/blog/tag/{}", tag.slug), "weekly")
+                .lastmod(newest_post(&content.posts_by_tag(&tag.slug)))
+        })
+        .collect();
+    urls.push(url("/blog/tags", "weekly").lastmod(tags.iter().filter_map(|t| t.lastmod).max()));
+    urls.extend(tags);
+    for lang in &state.config.languages[1..] {
+        for tag in content.all_tags_in(lang) {
+            urls.push(
+                url(&format!("/{}/blog/tag/{}", lang, tag.slug), "weekly")
+                    .lastmod(newest_post(&content.posts_by_tag_in(lang, &tag.slug))),
+            );
+        }
+    }
+
+    urls
+}
+
+/// `/sitemap.xml`: the sitemap itself, or a sitemap index once the site
+/// outgrows a single file
+pub async fn sitemap(State(state): State<AppState>) -> Response {
+    let content = state.content.read().await;
+    let urls = sitemap_urls(&state, &content);
+    let last_modified = urls.iter().filter_map(|u| u.lastmod).max();
+
+    let xml = if sitemap::page_count(urls.len()) == 0 {
+        sitemap::urlset(&urls)
+    } else {
+        let sitemaps: Vec<_> = urls
+            .chunks(sitemap::MAX_URLS)
+            .enumerate()
+            .map(|(i, chunk)| {
+                (
+                    format!("{}/sitemaps/{}.xml", state.config.site_url, i + 1),
+                    chunk.iter().filter_map(|u| u.lastmod).max(),
+                )
+            })
+            .collect();
+        sitemap::index(&sitemaps)
+    };
+
+    sitemap_response(last_modified, xml)
+}
+
+/// `/sitemaps/{n}.xml`: one part of a split sitemap
+pub async fn sitemap_part(
+    State(state): State<AppState>,
+    Path(file): Path<String>,
+) -> Result<Response, StatusCode> {
+    let number: usize = file
+        .strip_suffix(".xml")
+        .and_then(|n| n.parse().ok())
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let content = state.content.read().await;
+    let urls = sitemap_urls(&state, &content);
+    if number == 0 || number > sitemap::page_count(urls.len()) {
+        return Err(StatusCode::NOT_FOUND);
     }
+    let chunk = urls
+        .chunks(sitemap::MAX_URLS)
+        .nth(number - 1)
+        .ok_or(StatusCode::NOT_FOUND)?;
 
-    xml.push_str("</urlset>");
+    Ok(sitemap_response(
+        chunk.iter().filter_map(|u| u.lastmod).max(),
+        sitemap::urlset(chunk),
+    ))
+}
 
+fn sitemap_response(last_modified: Option<DateTime<Utc>>, xml: String) -> Response {
     (
+        last_modified.map(|m| Extension(LastModified(m))),
         [(header::CONTENT_TYPE, "application/xml; charset=utf-8")],
         xml,
     )
@@ -3,6 +3,7 @@ use axum::http::StatusCode;
 use axum::response::{Html, Json};
 use serde::{Deserialize, Serialize};
 
+use crate::cache::CacheStats;
 use crate::state::AppState;
 
 #[derive(Serialize)]
@@ -11,6 +12,7 @@ pub struct HealthResponse {
     database: &'static str,
     posts_count: usize,
     projects_count: usize,
+    page_cache: CacheStats,
 }
 
 pub async fn check(State(state): State<AppState>) -> Result<Json<HealthResponse>, StatusCode> {
@@ -28,6 +30,7 @@ pub async fn check(State(state): State<AppState>) -> Result<Json<HealthResponse>
         database: db_status,
         posts_count: content.posts.len(),
         projects_count: content.projects.len(),
+        page_cache: state.page_cache.stats(),
     }))
 }
 
@@ -1,8 +1,27 @@
 pub mod admin;
+pub mod assets;
+pub mod authors;
 pub mod blog;
+pub mod cache;
+pub mod collections;
+pub mod conditional;
 pub mod contact;
 pub mod feeds;
 pub mod health;
+pub mod og;
 pub mod pages;
 pub mod projects;
+pub mod redirects;
 pub mod resume;
+pub mod robots;
+pub mod search;
+pub mod views;
+pub mod webmentions;
+
+use axum::http::HeaderMap;
+
+/// Check if a request came from HTMX and only needs a page fragment.
+/// History restores after a cache miss still get the full page.
+pub(crate) fn wants_fragment(headers: &HeaderMap) -> bool {
+    headers.contains_key("hx-request") && !headers.contains_key("hx-history-restore-request")
+}
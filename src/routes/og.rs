@@ -0,0 +1,86 @@
+use axum::extract::{Path, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+
+use crate::i18n::{Lang, Translator};
+use crate::og::OgCard;
+use crate::state::AppState;
+
+/// `/og/blog/{slug}.png`: card for a post in the requested language
+pub async fn post(
+    State(state): State<AppState>,
+    lang: Lang,
+    Path(file): Path<String>,
+) -> Result<Response, StatusCode> {
+    let slug = file.strip_suffix(".png").ok_or(StatusCode::NOT_FOUND)?;
+    let card = {
+        let content = state.content.read().await;
+        let post = content
+            .post(&lang.code, slug)
+            .filter(|p| !p.metadata.draft)
+            .ok_or(StatusCode::NOT_FOUND)?;
+        OgCard {
+            title: post.metadata.title.clone(),
+            kicker: Some(post.metadata.date.format("%Y-%m-%d").to_string()),
+            tags: content
+                .tag_links(post)
+                .into_iter()
+                .map(|t| t.name.to_string())
+                .collect(),
+        }
+    };
+
+    png(&state, card).await
+}
+
+/// `/og/projects/{slug}.png`. Projects are in the default language, and so
+/// is the kicker, whatever the visitor's UI language.
+pub async fn project(
+    State(state): State<AppState>,
+    Path(file): Path<String>,
+) -> Result<Response, StatusCode> {
+    let slug = file.strip_suffix(".png").ok_or(StatusCode::NOT_FOUND)?;
+    let card = {
+        let content = state.content.read().await;
+        let t = Translator::new(state.catalog.clone(), &content.default_language);
+        let project = content.projects.get(slug).ok_or(StatusCode::NOT_FOUND)?;
+        OgCard {
+            title: project.metadata.title.clone(),
+            kicker: Some(t.get("nav-projects")),
+            tags: project.metadata.tags.clone(),
+        }
+    };
+
+    png(&state, card).await
+}
+
+/// `/og/site.png`: generic card for list pages
+pub async fn site(State(state): State<AppState>) -> Result<Response, StatusCode> {
+    let card = OgCard {
+        title: state.config.site_description.clone(),
+        kicker: None,
+        tags: Vec::new(),
+    };
+
+    png(&state, card).await
+}
+
+async fn png(state: &AppState, card: OgCard) -> Result<Response, StatusCode> {
+    let renderer = state.og.clone();
+    let png = tokio::task::spawn_blocking(move || renderer.render(&card))
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .map_err(|e| {
+            tracing::error!("Failed to render Open Graph image: {:#}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "image/png"),
+            (header::CACHE_CONTROL, "public, max-age=86400"),
+        ],
+        png,
+    )
+        .into_response())
+}
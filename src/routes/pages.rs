@@ -1,23 +1,46 @@
 use askama::Template;
-use axum::extract::State;
-use axum::response::Html;
+use axum::extract::{OriginalUri, Path, Query, State};
+use axum::http::header::{REFERER, SET_COOKIE};
+use axum::http::{HeaderMap, StatusCode, Uri};
+use axum::response::{Html, IntoResponse, Redirect, Response};
 
+use crate::i18n::catalog::LOCALE_COOKIE;
+use crate::i18n::Translator;
+use crate::og;
+use crate::pagination::PageQuery;
 use crate::state::AppState;
 use crate::VERSION;
 
 #[derive(Template)]
 #[template(path = "index.html")]
 struct IndexTemplate<'a> {
+    t: &'a Translator,
     title: &'a str,
     nav_path: &'a str,
     version: &'a str,
     canonical_url: String,
     og_type: &'a str,
     og_image: Option<String>,
+    json_ld: Option<String>,
     recent_posts: Vec<PostSummary<'a>>,
     featured_projects: Vec<ProjectSummary<'a>>,
 }
 
+#[derive(Template)]
+#[template(path = "page.html")]
+struct PageTemplate<'a> {
+    t: &'a Translator,
+    title: &'a str,
+    nav_path: &'a str,
+    version: &'a str,
+    canonical_url: String,
+    og_type: &'a str,
+    og_image: Option<String>,
+    json_ld: Option<String>,
+    description: Option<&'a str>,
+    content: &'a str,
+}
+
 struct PostSummary<'a> {
     title: &'a str,
     slug: &'a str,
@@ -32,11 +55,12 @@ struct ProjectSummary<'a> {
     slug: &'a str,
     description: Option<&'a str>,
     status: &'a str,
+    status_label: String,
     cover_image: Option<String>,
     tags: &'a [String],
 }
 
-pub async fn index(State(state): State<AppState>) -> Html<String> {
+pub async fn index(State(state): State<AppState>, t: Translator) -> Html<String> {
     let content = state.content.read().await;
 
     let recent_posts: Vec<_> = content
@@ -74,12 +98,8 @@ pub async fn index(State(state): State<AppState>) -> Html<String> {
                 title: &p.metadata.title,
                 slug: &p.metadata.slug,
                 description: p.metadata.description.as_deref(),
-                status: match p.metadata.status {
-                    crate::models::project::ProjectStatus::Active => "Active",
-                    crate::models::project::ProjectStatus::Completed => "Completed",
-                    crate::models::project::ProjectStatus::Archived => "Archived",
-                    crate::models::project::ProjectStatus::Planned => "Planned",
-                },
+                status: super::projects::status_label(&p.metadata.status),
+                status_label: super::projects::localized_status(&t, &p.metadata.status),
                 cover_image,
                 tags: &p.metadata.tags,
             }
@@ -87,12 +107,14 @@ pub async fn index(State(state): State<AppState>) -> Html<String> {
         .collect();
 
     let template = IndexTemplate {
+        t: &t,
         title: &state.config.site_title,
         nav_path: "/",
         version: VERSION,
         canonical_url: state.config.site_url.clone(),
         og_type: "website",
-        og_image: None,
+        og_image: Some(og::site_image(&state.config)),
+        json_ld: None,
         recent_posts,
         featured_projects,
     };
@@ -103,3 +125,73 @@ pub async fn index(State(state): State<AppState>) -> Html<String> {
             .unwrap_or_else(|e| format!("Error: {}", e)),
     )
 }
+
+/// Remember the chosen UI language in a cookie and return to the previous page
+pub async fn set_locale(
+    State(state): State<AppState>,
+    Path(code): Path<String>,
+    headers: HeaderMap,
+) -> Response {
+    if !state.catalog.has_locale(&code) {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+
+    // Only the path of the referring page is used, so this can't redirect off-site
+    let back = headers
+        .get(REFERER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|r| r.parse::<Uri>().ok())
+        .and_then(|uri| uri.path_and_query().map(|pq| pq.to_string()))
+        .filter(|path| path.starts_with('/') && !path.starts_with("//"))
+        .unwrap_or_else(|| "/".to_string());
+
+    let cookie = format!(
+        "{}={}; Path=/; Max-Age=31536000; SameSite=Lax",
+        LOCALE_COOKIE, code
+    );
+
+    ([(SET_COOKIE, cookie)], Redirect::to(&back)).into_response()
+}
+
+/// Serve markdown pages from `content/pages/` and collections. Both are
+/// looked up per request, so new paths work after a content reload.
+pub async fn fallback(
+    State(state): State<AppState>,
+    t: Translator,
+    OriginalUri(uri): OriginalUri,
+    query: Query<PageQuery>,
+    headers: HeaderMap,
+) -> Result<Html<String>, StatusCode> {
+    let path = uri.path().trim_end_matches('/');
+
+    let rendered = {
+        let content = state.content.read().await;
+        content.page(path).map(|page| {
+            let og_image = page
+                .metadata
+                .cover_image
+                .as_ref()
+                .map(|c| state.config.resolve_cover_url(c));
+
+            PageTemplate {
+                t: &t,
+                title: &page.metadata.title,
+                nav_path: &page.metadata.path,
+                version: VERSION,
+                canonical_url: format!("{}{}", state.config.site_url, page.metadata.path),
+                og_type: "website",
+                og_image,
+                json_ld: None,
+                description: page.metadata.description.as_deref(),
+                content: &page.content_html,
+            }
+            .render()
+            .unwrap_or_else(|e| format!("Error: {}", e))
+        })
+    };
+
+    match rendered {
+        Some(html) => Ok(Html(html)),
+        None => super::collections::show(State(state), t, OriginalUri(uri), query, headers).await,
+    }
+}
@@ -1,45 +1,64 @@
 use askama::Template;
-use axum::extract::{ConnectInfo, Path, State};
+use axum::extract::{Path, Query, State};
 use axum::http::{HeaderMap, StatusCode};
 use axum::response::Html;
-use std::net::SocketAddr;
 
+use crate::i18n::Translator;
 use crate::models::project::ProjectStatus;
+use crate::models::Project;
+use crate::og;
+use crate::pagination::{PageQuery, Pagination};
 use crate::state::AppState;
+use crate::structured_data::{Agent, BreadcrumbList, Graph, Node, Person, SoftwareSourceCode};
 use crate::views::{self, ContentType, ViewsService};
 use crate::VERSION;
 
 #[derive(Template)]
 #[template(path = "projects/list.html")]
 struct ProjectListTemplate<'a> {
+    t: &'a Translator,
     title: &'a str,
     nav_path: &'a str,
     version: &'a str,
     canonical_url: String,
     og_type: &'a str,
     og_image: Option<String>,
+    json_ld: Option<String>,
+    site_url: &'a str,
+    projects: Vec<ProjectItem<'a>>,
+    pagination: Pagination,
+}
+
+/// Next page of project cards for HTMX "load more"
+#[derive(Template)]
+#[template(path = "partials/project-items.html")]
+struct ProjectItemsTemplate<'a> {
+    t: &'a Translator,
     projects: Vec<ProjectItem<'a>>,
+    pagination: Pagination,
 }
 
 #[derive(Template)]
 #[template(path = "projects/show.html")]
 struct ProjectShowTemplate<'a> {
+    t: &'a Translator,
     title: &'a str,
     nav_path: &'a str,
     version: &'a str,
     canonical_url: String,
     og_type: &'a str,
     og_image: Option<String>,
+    json_ld: Option<String>,
     description: Option<&'a str>,
     status: &'a str,
+    status_label: String,
     github_url: Option<&'a str>,
     demo_url: Option<&'a str>,
     tags: &'a [String],
     content: &'a str,
     cover_image: Option<String>,
-    views_count: Option<String>,
-    site_url: &'a str,
-    author_name: &'a str,
+    /// HTMX endpoint that records a view and returns the counter
+    views_url: Option<String>,
 }
 
 struct ProjectItem<'a> {
@@ -47,13 +66,15 @@ struct ProjectItem<'a> {
     slug: &'a str,
     description: Option<&'a str>,
     status: &'a str,
+    status_label: String,
     github_url: Option<&'a str>,
     tags: &'a [String],
     cover_image: Option<String>,
     views_count: Option<String>,
 }
 
-fn status_label(status: &ProjectStatus) -> &'static str {
+/// English status name, also used as the CSS class
+pub(super) fn status_label(status: &ProjectStatus) -> &'static str {
     match status {
         ProjectStatus::Active => "Active",
         ProjectStatus::Completed => "Completed",
@@ -62,14 +83,33 @@ fn status_label(status: &ProjectStatus) -> &'static str {
     }
 }
 
-pub async fn list(State(state): State<AppState>) -> Html<String> {
+/// Status name in the visitor's language
+pub(super) fn localized_status(t: &Translator, status: &ProjectStatus) -> String {
+    t.get(&format!("status-{}", status_label(status).to_lowercase()))
+}
+
+pub async fn list(
+    State(state): State<AppState>,
+    t: Translator,
+    Query(query): Query<PageQuery>,
+    headers: HeaderMap,
+) -> Result<Html<String>, StatusCode> {
     let content = state.content.read().await;
     let all_projects = content.all_projects();
 
+    let pagination = Pagination::new(
+        "/projects",
+        all_projects.len(),
+        state.config.page_size,
+        query.page,
+    )
+    .ok_or(StatusCode::NOT_FOUND)?;
+    let page_projects = &all_projects[pagination.range(all_projects.len())];
+
     // Batch fetch view counts if Redis is available
     let view_counts: Vec<Option<String>> = if let Some(ref redis) = state.redis {
         let service = ViewsService::new(redis.clone());
-        let slugs: Vec<&str> = all_projects
+        let slugs: Vec<&str> = page_projects
             .iter()
             .map(|p| p.metadata.slug.as_str())
             .collect();
@@ -78,14 +118,14 @@ pub async fn list(State(state): State<AppState>) -> Html<String> {
                 .into_iter()
                 .map(|c| Some(views::format_count(c)))
                 .collect(),
-            Err(_) => vec![None; all_projects.len()],
+            Err(_) => vec![None; page_projects.len()],
         }
     } else {
-        vec![None; all_projects.len()]
+        vec![None; page_projects.len()]
     };
 
-    let projects: Vec<_> = all_projects
-        .into_iter()
+    let projects: Vec<_> = page_projects
+        .iter()
         .zip(view_counts)
         .map(|(p, views_count)| {
             let cover_image = p
@@ -98,6 +138,7 @@ pub async fn list(State(state): State<AppState>) -> Html<String> {
                 slug: &p.metadata.slug,
                 description: p.metadata.description.as_deref(),
                 status: status_label(&p.metadata.status),
+                status_label: localized_status(&t, &p.metadata.status),
                 github_url: p.metadata.github_url.as_deref(),
                 tags: &p.metadata.tags,
                 cover_image,
@@ -106,60 +147,87 @@ pub async fn list(State(state): State<AppState>) -> Html<String> {
         })
         .collect();
 
-    let template = ProjectListTemplate {
-        title: "Projects",
-        nav_path: "/projects",
-        version: VERSION,
-        canonical_url: format!("{}/projects", state.config.site_url),
-        og_type: "website",
-        og_image: None,
-        projects,
+    let rendered = if super::wants_fragment(&headers) {
+        ProjectItemsTemplate {
+            t: &t,
+            projects,
+            pagination,
+        }
+        .render()
+    } else {
+        ProjectListTemplate {
+            t: &t,
+            title: &t.get("projects-title"),
+            nav_path: "/projects",
+            version: VERSION,
+            canonical_url: format!("{}{}", state.config.site_url, pagination.current_url()),
+            og_type: "website",
+            og_image: Some(og::site_image(&state.config)),
+            json_ld: None,
+            site_url: &state.config.site_url,
+            projects,
+            pagination,
+        }
+        .render()
     };
 
-    Html(
-        template
-            .render()
-            .unwrap_or_else(|e| format!("Error: {}", e)),
-    )
+    Ok(Html(rendered.unwrap_or_else(|e| format!("Error: {}", e))))
+}
+
+/// `SoftwareSourceCode` for projects with a repository, and breadcrumbs
+fn project_json_ld(state: &AppState, t: &Translator, project: &Project) -> String {
+    let config = &state.config;
+    let url = format!("{}/projects/{}", config.site_url, project.metadata.slug);
+    let mut nodes = Vec::new();
+
+    if let Some(repository) = &project.metadata.github_url {
+        nodes.push(Node::SoftwareSourceCode(SoftwareSourceCode {
+            name: project.metadata.title.clone(),
+            description: project.metadata.description.clone(),
+            url: url.clone(),
+            code_repository: repository.clone(),
+            date_created: project.metadata.date,
+            date_modified: project.last_modified(),
+            author: Agent::Person(Person {
+                name: config.author_name.clone(),
+                url: Some(config.site_url.clone()),
+                ..Person::default()
+            }),
+            image: project
+                .metadata
+                .cover_image
+                .as_deref()
+                .map(|c| config.absolute_url(&config.resolve_cover_url(c))),
+            keywords: project.metadata.tags.clone(),
+        }));
+    }
+    nodes.push(Node::BreadcrumbList(BreadcrumbList::new(vec![
+        (t.get("nav-home"), config.site_url.clone()),
+        (
+            t.get("nav-projects"),
+            format!("{}/projects", config.site_url),
+        ),
+        (project.metadata.title.clone(), url),
+    ])));
+
+    Graph::new(nodes).to_json()
 }
 
 pub async fn show(
     State(state): State<AppState>,
+    t: Translator,
     Path(slug): Path<String>,
-    headers: HeaderMap,
-    peer: Option<ConnectInfo<SocketAddr>>,
 ) -> Result<Html<String>, StatusCode> {
     let content = state.content.read().await;
 
     let project = content.projects.get(&slug).ok_or(StatusCode::NOT_FOUND)?;
 
-    // Get views count and record view if Redis is available
-    let views_count = if let Some(ref redis) = state.redis {
-        let service = ViewsService::new(redis.clone());
-
-        // Get current count first (for display)
-        let count = service
-            .get_count(ContentType::Project, &slug)
-            .await
-            .unwrap_or(0);
-
-        // Record view in background (fire and forget)
-        let ip = views::extract_client_ip(&headers, peer.map(|p| p.0));
-        let ua = views::extract_user_agent(&headers);
-        if let Some(ip) = ip {
-            let service = ViewsService::new(redis.clone());
-            let slug_owned = slug.clone();
-            tokio::spawn(async move {
-                let _ = service
-                    .record_view(ContentType::Project, &slug_owned, &ip, ua.as_deref())
-                    .await;
-            });
-        }
-
-        Some(views::format_count(count))
-    } else {
-        None
-    };
+    // The counter is loaded (and the view recorded) separately, so the
+    // page itself can be cached
+    let views_url = state
+        .redis
+        .is_some()
+        .then(|| format!("/views/project/{}", slug));
 
     let cover_image = project
         .metadata
@@ -168,22 +236,25 @@ pub async fn show(
         .map(|c| state.config.resolve_cover_url(c));
 
     let template = ProjectShowTemplate {
+        t: &t,
         title: &project.metadata.title,
         nav_path: "/projects",
         version: VERSION,
         canonical_url: format!("{}/projects/{}", state.config.site_url, slug),
         og_type: "website",
-        og_image: cover_image.clone(),
+        og_image: cover_image
+            .clone()
+            .or_else(|| Some(og::project_image(&state.config, &slug))),
+        json_ld: Some(project_json_ld(&state, &t, project)),
         description: project.metadata.description.as_deref(),
         status: status_label(&project.metadata.status),
+        status_label: localized_status(&t, &project.metadata.status),
         github_url: project.metadata.github_url.as_deref(),
         demo_url: project.metadata.demo_url.as_deref(),
         tags: &project.metadata.tags,
         content: &project.content_html,
         cover_image,
-        views_count,
-        site_url: &state.config.site_url,
-        author_name: &state.config.author_name,
+        views_url,
     };
 
     Ok(Html(
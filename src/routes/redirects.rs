@@ -0,0 +1,29 @@
+use axum::extract::{Request, State};
+use axum::http::header::LOCATION;
+use axum::http::StatusCode;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use percent_encoding::percent_decode_str;
+
+use crate::content::redirects::Target;
+use crate::state::AppState;
+
+/// Answer requests for aliased or removed URLs with 301 or 410 before routing
+pub async fn redirect(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let path = percent_decode_str(request.uri().path()).decode_utf8_lossy();
+    let target = state.content.read().await.redirects.get(&path).cloned();
+
+    match target {
+        Some(Target::Moved(mut location)) => {
+            // Keep the query string unless the target sets its own
+            if let Some(query) = request.uri().query() {
+                if !location.contains('?') {
+                    location = format!("{}?{}", location, query);
+                }
+            }
+            (StatusCode::MOVED_PERMANENTLY, [(LOCATION, location)]).into_response()
+        }
+        Some(Target::Gone) => (StatusCode::GONE, "Gone").into_response(),
+        None => next.run(request).await,
+    }
+}
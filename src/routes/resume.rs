@@ -1,38 +1,283 @@
 use askama::Template;
 use axum::extract::State;
-use axum::response::Html;
+use axum::http::{header, StatusCode};
+use axum::response::{Html, IntoResponse, Response};
+use axum::Json;
 
+use crate::content::archive::month_id;
+use crate::content::markdown::render_markdown;
+use crate::i18n::Translator;
+use crate::models::resume::{ResumeProject, Skill};
+use crate::models::Resume;
 use crate::state::AppState;
+use crate::structured_data::{Graph, Node, Person};
 use crate::VERSION;
 
 #[derive(Template)]
 #[template(path = "resume.html")]
 struct ResumeTemplate<'a> {
+    t: &'a Translator,
     title: &'a str,
     nav_path: &'a str,
     version: &'a str,
     canonical_url: String,
     og_type: &'a str,
     og_image: Option<&'a str>,
-    author_name: &'a str,
-    author_email: &'a str,
+    json_ld: Option<String>,
+    print: bool,
+    resume: &'a Resume,
+    email: &'a str,
+    summary_html: Option<String>,
+    skills: &'a [Skill],
+    work: Vec<WorkItem<'a>>,
+    education: Vec<EducationItem<'a>>,
+    certificates: Vec<CertificateItem<'a>>,
+    projects: &'a [ResumeProject],
 }
 
-pub async fn show(State(state): State<AppState>) -> Html<String> {
+/// Same content as [`ResumeTemplate`] on a single page without site chrome
+#[derive(Template)]
+#[template(path = "resume_print.html")]
+struct ResumePrintTemplate<'a> {
+    t: &'a Translator,
+    title: &'a str,
+    canonical_url: String,
+    print: bool,
+    resume: &'a Resume,
+    email: &'a str,
+    summary_html: Option<String>,
+    skills: &'a [Skill],
+    work: Vec<WorkItem<'a>>,
+    education: Vec<EducationItem<'a>>,
+    certificates: Vec<CertificateItem<'a>>,
+    projects: &'a [ResumeProject],
+}
+
+struct WorkItem<'a> {
+    name: &'a str,
+    position: Option<&'a str>,
+    location: Option<&'a str>,
+    url: Option<&'a str>,
+    dates: String,
+    summary: Option<&'a str>,
+    highlights: &'a [String],
+    keywords: String,
+}
+
+struct EducationItem<'a> {
+    institution: &'a str,
+    degree: Option<String>,
+    courses: &'a [String],
+    dates: String,
+}
+
+struct CertificateItem<'a> {
+    name: &'a str,
+    issuer: Option<&'a str>,
+    date: Option<String>,
+    url: Option<&'a str>,
+}
+
+/// `2021-11` → "November 2021"; bare years are kept as they are
+fn display_date(t: &Translator, date: &str) -> String {
+    let mut parts = date.split('-');
+    let year = parts.next().unwrap_or_default();
+    match parts.next().and_then(|m| m.parse::<u32>().ok()) {
+        Some(month @ 1..=12) => format!("{} {}", t.get(&month_id(month)), year),
+        _ => year.to_string(),
+    }
+}
+
+/// "November 2021 - Present"; an open range means a current position
+fn date_range(t: &Translator, start: Option<&str>, end: Option<&str>) -> String {
+    match (start, end) {
+        (Some(start), Some(end)) => {
+            format!("{} - {}", display_date(t, start), display_date(t, end))
+        }
+        (Some(start), None) => format!("{} - {}", display_date(t, start), t.get("resume-present")),
+        (None, Some(end)) => display_date(t, end),
+        (None, None) => String::new(),
+    }
+}
+
+fn work_items<'a>(t: &Translator, resume: &'a Resume) -> Vec<WorkItem<'a>> {
+    resume
+        .work
+        .iter()
+        .map(|w| WorkItem {
+            name: &w.name,
+            position: w.position.as_deref(),
+            location: w.location.as_deref(),
+            url: w.url.as_deref(),
+            dates: date_range(t, w.start_date.as_deref(), w.end_date.as_deref()),
+            summary: w.summary.as_deref(),
+            highlights: &w.highlights,
+            keywords: w.keywords.join(", "),
+        })
+        .collect()
+}
+
+fn education_items<'a>(t: &Translator, resume: &'a Resume) -> Vec<EducationItem<'a>> {
+    resume
+        .education
+        .iter()
+        .map(|e| EducationItem {
+            institution: &e.institution,
+            degree: match (&e.study_type, &e.area) {
+                (Some(kind), Some(area)) => Some(format!("{}, {}", kind, area)),
+                (kind, area) => kind.clone().or_else(|| area.clone()),
+            },
+            courses: &e.courses,
+            dates: date_range(t, e.start_date.as_deref(), e.end_date.as_deref()),
+        })
+        .collect()
+}
+
+fn certificate_items<'a>(t: &Translator, resume: &'a Resume) -> Vec<CertificateItem<'a>> {
+    resume
+        .certificates
+        .iter()
+        .map(|c| CertificateItem {
+            name: &c.name,
+            issuer: c.issuer.as_deref(),
+            date: c.date.as_deref().map(|d| display_date(t, d)),
+            url: c.url.as_deref(),
+        })
+        .collect()
+}
+
+/// Resume with `basics.email` defaulting to the site author's address
+fn resume_with_defaults(state: &AppState, resume: &Resume) -> Resume {
+    let mut resume = resume.clone();
+    resume
+        .basics
+        .email
+        .get_or_insert_with(|| state.config.author_email.clone());
+    resume
+}
+
+/// `Person` from the resume basics
+fn person_json_ld(state: &AppState, resume: &Resume) -> String {
+    let basics = &resume.basics;
+    let person = Person {
+        name: basics.name.clone(),
+        url: Some(
+            basics
+                .url
+                .clone()
+                .unwrap_or_else(|| state.config.site_url.clone()),
+        ),
+        image: basics
+            .image
+            .as_deref()
+            .map(|i| state.config.absolute_url(i)),
+        job_title: basics.label.clone(),
+        email: Some(
+            basics
+                .email
+                .clone()
+                .unwrap_or_else(|| state.config.author_email.clone()),
+        ),
+        same_as: basics.profiles.iter().map(|p| p.url.clone()).collect(),
+    };
+
+    Graph::new(vec![Node::Person(person)]).to_json()
+}
+
+pub async fn show(
+    State(state): State<AppState>,
+    t: Translator,
+) -> Result<Html<String>, StatusCode> {
+    let content = state.content.read().await;
+    let resume = content.resume.as_ref().ok_or(StatusCode::NOT_FOUND)?;
+
     let template = ResumeTemplate {
-        title: "Resume",
+        t: &t,
+        title: &t.get("resume-title"),
         nav_path: "/resume",
         version: VERSION,
         canonical_url: format!("{}/resume", state.config.site_url),
         og_type: "website",
         og_image: None,
-        author_name: &state.config.author_name,
-        author_email: &state.config.author_email,
+        json_ld: Some(person_json_ld(&state, resume)),
+        print: false,
+        resume,
+        email: resume
+            .basics
+            .email
+            .as_deref()
+            .unwrap_or(&state.config.author_email),
+        summary_html: resume.basics.summary.as_deref().map(render_markdown),
+        skills: &resume.skills,
+        work: work_items(&t, resume),
+        education: education_items(&t, resume),
+        certificates: certificate_items(&t, resume),
+        projects: &resume.projects,
     };
 
-    Html(
+    Ok(Html(
         template
             .render()
             .unwrap_or_else(|e| format!("Error: {}", e)),
+    ))
+}
+
+/// Print-optimised resume at `/resume/print`
+pub async fn print(
+    State(state): State<AppState>,
+    t: Translator,
+) -> Result<Html<String>, StatusCode> {
+    let content = state.content.read().await;
+    let resume = content.resume.as_ref().ok_or(StatusCode::NOT_FOUND)?;
+
+    let template = ResumePrintTemplate {
+        t: &t,
+        title: &t.get("resume-title"),
+        canonical_url: format!("{}/resume", state.config.site_url),
+        print: true,
+        resume,
+        email: resume
+            .basics
+            .email
+            .as_deref()
+            .unwrap_or(&state.config.author_email),
+        summary_html: resume.basics.summary.as_deref().map(render_markdown),
+        skills: &resume.skills,
+        work: work_items(&t, resume),
+        education: education_items(&t, resume),
+        certificates: certificate_items(&t, resume),
+        projects: &resume.projects,
+    };
+
+    Ok(Html(
+        template
+            .render()
+            .unwrap_or_else(|e| format!("Error: {}", e)),
+    ))
+}
+
+/// Resume in the JSON Resume format at `/resume.json`
+pub async fn json(State(state): State<AppState>) -> Result<Json<Resume>, StatusCode> {
+    let content = state.content.read().await;
+    let resume = content.resume.as_ref().ok_or(StatusCode::NOT_FOUND)?;
+
+    Ok(Json(resume_with_defaults(&state, resume)))
+}
+
+/// Contact card at `/resume.vcf`
+pub async fn vcard(State(state): State<AppState>) -> Result<Response, StatusCode> {
+    let content = state.content.read().await;
+    let resume = content.resume.as_ref().ok_or(StatusCode::NOT_FOUND)?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/vcard; charset=utf-8"),
+            (
+                header::CONTENT_DISPOSITION,
+                "attachment; filename=\"resume.vcf\"",
+            ),
+        ],
+        resume_with_defaults(&state, resume).to_vcard(),
     )
+        .into_response())
 }
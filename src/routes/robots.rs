@@ -0,0 +1,73 @@
+use axum::extract::{Request, State};
+use axum::http::{header, HeaderValue};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+
+use crate::state::AppState;
+
+/// Draft previews, disallowed whatever the configured rules say. Drafts
+/// themselves aren't listed: their URLs 404, and naming them here would
+/// publish the slugs.
+const PREVIEW_PREFIX: &str = "/preview/";
+
+/// robots.txt from the configured rules. Blocked user agents get their own
+/// group, so they skip the rules for everyone else.
+pub async fn robots(State(state): State<AppState>) -> Response {
+    let mut disallow = state.config.robots_disallow.clone();
+    disallow.push(PREVIEW_PREFIX.to_string());
+
+    let body = robots_txt(
+        &state.config.robots_blocked_agents,
+        &disallow,
+        &format!("{}/sitemap.xml", state.config.site_url),
+    );
+
+    ([(header::CONTENT_TYPE, "text/plain; charset=utf-8")], body).into_response()
+}
+
+fn robots_txt(blocked_agents: &[String], disallow: &[String], sitemap_url: &str) -> String {
+    let mut body = String::new();
+    for agent in blocked_agents {
+        body.push_str(&format!("User-agent: {}\nDisallow: /\n\n", agent));
+    }
+
+    body.push_str("User-agent: *\n");
+    for path in disallow {
+        body.push_str(&format!("Disallow: {}\n", path));
+    }
+    body.push_str("Allow: /\n\n");
+    body.push_str(&format!("Sitemap: {}\n", sitemap_url));
+    body
+}
+
+/// Route layer for pages that must not be indexed; mirrors their
+/// `<meta name="robots" content="noindex">` for non-HTML-aware crawlers
+pub async fn noindex(request: Request, next: Next) -> Response {
+    let mut response = next.run(request).await;
+    response
+        .headers_mut()
+        .insert("x-robots-tag", HeaderValue::from_static("noindex"));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_robots_txt() {
+        let body = robots_txt(
+            &["GPTBot".into(), "CCBot".into()],
+            &["/admin".into(), "/preview/".into()],
+            "https://example.com/sitemap.xml",
+        );
+
+        assert_eq!(
+            body,
+            "User-agent: GPTBot\nDisallow: /\n\n\
+             User-agent: CCBot\nDisallow: /\n\n\
+             User-agent: *\nDisallow: /admin\nDisallow: /preview/\nAllow: /\n\n\
+             Sitemap: https://example.com/sitemap.xml\n"
+        );
+    }
+}
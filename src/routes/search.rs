@@ -0,0 +1,97 @@
+use askama::Template;
+use axum::extract::{Query, State};
+use axum::http::HeaderMap;
+use axum::response::Html;
+use serde::Deserialize;
+
+use crate::i18n::Translator;
+use crate::state::AppState;
+use crate::VERSION;
+
+/// Maximum number of results returned for a query
+const MAX_RESULTS: usize = 20;
+
+#[derive(Template)]
+#[template(path = "search.html")]
+struct SearchTemplate<'a> {
+    t: &'a Translator,
+    title: &'a str,
+    nav_path: &'a str,
+    version: &'a str,
+    canonical_url: String,
+    og_type: &'a str,
+    og_image: Option<String>,
+    json_ld: Option<String>,
+    query: &'a str,
+    results: Vec<ResultItem<'a>>,
+}
+
+/// Results fragment for HTMX live search
+#[derive(Template)]
+#[template(path = "partials/search-results.html")]
+struct SearchResultsTemplate<'a> {
+    t: &'a Translator,
+    query: &'a str,
+    results: Vec<ResultItem<'a>>,
+}
+
+struct ResultItem<'a> {
+    kind: String,
+    title: &'a str,
+    url: &'a str,
+    snippet: String,
+}
+
+#[derive(Deserialize)]
+pub struct SearchQuery {
+    #[serde(default)]
+    q: String,
+}
+
+pub async fn search(
+    State(state): State<AppState>,
+    t: Translator,
+    Query(query): Query<SearchQuery>,
+    headers: HeaderMap,
+) -> Html<String> {
+    let content = state.content.read().await;
+    let q = query.q.trim();
+
+    let results: Vec<_> = content
+        .search
+        .search(q, MAX_RESULTS)
+        .into_iter()
+        .map(|hit| ResultItem {
+            kind: t.get(hit.doc.kind.label_id()),
+            title: &hit.doc.title,
+            url: &hit.doc.url,
+            snippet: hit.snippet,
+        })
+        .collect();
+
+    // HTMX requests only need the results list
+    let rendered = if super::wants_fragment(&headers) {
+        SearchResultsTemplate {
+            t: &t,
+            query: q,
+            results,
+        }
+        .render()
+    } else {
+        SearchTemplate {
+            t: &t,
+            title: &t.get("search-title"),
+            nav_path: "/search",
+            version: VERSION,
+            canonical_url: format!("{}/search", state.config.site_url),
+            og_type: "website",
+            og_image: None,
+            json_ld: None,
+            query: q,
+            results,
+        }
+        .render()
+    };
+
+    Html(rendered.unwrap_or_else(|e| format!("Error: {}", e)))
+}
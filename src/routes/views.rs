@@ -0,0 +1,62 @@
+use askama::Template;
+use axum::extract::{ConnectInfo, Path, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::Html;
+use std::net::SocketAddr;
+
+use crate::state::AppState;
+use crate::views::{self, ContentType, ViewsService};
+
+/// Views counter swapped into post and project pages
+#[derive(Template)]
+#[template(path = "partials/views-count.html")]
+struct ViewsCountTemplate {
+    views: String,
+}
+
+/// Record a view and return the counter. Pages load this with HTMX, so they
+/// can be served from the page cache and still count every visit.
+pub async fn record(
+    State(state): State<AppState>,
+    Path((kind, key)): Path<(String, String)>,
+    headers: HeaderMap,
+    peer: Option<ConnectInfo<SocketAddr>>,
+) -> Result<Html<String>, StatusCode> {
+    let redis = state.redis.as_ref().ok_or(StatusCode::NOT_FOUND)?;
+
+    let content_type = {
+        let content = state.content.read().await;
+        match kind.as_str() {
+            "post" if content.posts.get(&key).is_some_and(|p| !p.metadata.draft) => {
+                ContentType::Post
+            }
+            "project" if content.projects.contains_key(&key) => ContentType::Project,
+            _ => return Err(StatusCode::NOT_FOUND),
+        }
+    };
+
+    let service = ViewsService::new(redis.clone());
+
+    // Get current count first (for display)
+    let count = service.get_count(content_type, &key).await.unwrap_or(0);
+
+    // Record view in background (fire and forget)
+    let ip = views::extract_client_ip(&headers, peer.map(|p| p.0));
+    let ua = views::extract_user_agent(&headers);
+    if let Some(ip) = ip {
+        tokio::spawn(async move {
+            let _ = service
+                .record_view(content_type, &key, &ip, ua.as_deref())
+                .await;
+        });
+    }
+
+    let template = ViewsCountTemplate {
+        views: views::format_count(count),
+    };
+    Ok(Html(
+        template
+            .render()
+            .unwrap_or_else(|e| format!("Error: {}", e)),
+    ))
+}
@@ -0,0 +1,139 @@
+use askama::Template;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::Html;
+use axum::Form;
+use serde::Deserialize;
+
+use crate::i18n::Translator;
+use crate::state::AppState;
+use crate::webmention::store::{self, Webmention};
+use crate::webmention::{self, Job};
+
+#[derive(Deserialize)]
+pub struct WebmentionForm {
+    #[serde(default)]
+    source: String,
+    #[serde(default)]
+    target: String,
+}
+
+/// Approved mentions swapped in under a post
+#[derive(Template)]
+#[template(path = "partials/webmentions.html")]
+struct WebmentionsTemplate<'a> {
+    t: &'a Translator,
+    /// `(heading message id, mentions)` for likes and reposts, shown as avatars
+    faces: Vec<(&'static str, Vec<Webmention>)>,
+    replies: Vec<Webmention>,
+    mentions: Vec<Webmention>,
+}
+
+/// Webmention endpoint. The request is validated and queued; the source is
+/// fetched and checked in the background, so this answers 202 right away.
+pub async fn receive(
+    State(state): State<AppState>,
+    Form(form): Form<WebmentionForm>,
+) -> (StatusCode, &'static str) {
+    if !state.config.webmentions {
+        return (StatusCode::NOT_FOUND, "Webmentions are disabled");
+    }
+
+    let Some(source) = webmention::parse_url(&form.source) else {
+        return (StatusCode::BAD_REQUEST, "source must be an http(s) URL");
+    };
+    let Some(target) = webmention::parse_url(&form.target) else {
+        return (StatusCode::BAD_REQUEST, "target must be an http(s) URL");
+    };
+    if source == target {
+        return (StatusCode::BAD_REQUEST, "source and target must differ");
+    }
+    if !webmention::is_public(&source) {
+        return (StatusCode::BAD_REQUEST, "source must be a public URL");
+    }
+
+    let on_site = webmention::parse_url(&state.config.site_url)
+        .is_some_and(|site| site.origin() == target.origin());
+    let post_key = if on_site {
+        let content = state.content.read().await;
+        let path = target.path().trim_end_matches('/');
+        content
+            .posts
+            .values()
+            .find(|p| !p.metadata.draft && p.path == path)
+            .map(|p| content.key_of(p))
+    } else {
+        None
+    };
+    let Some(post_key) = post_key else {
+        return (StatusCode::BAD_REQUEST, "target is not a post on this site");
+    };
+
+    let job = Job {
+        source,
+        target,
+        post_key,
+    };
+    if let Err(e) = state.webmentions.push(job) {
+        tracing::warn!("{}", e);
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Too many pending webmentions, try again later",
+        );
+    }
+    (StatusCode::ACCEPTED, "Webmention queued for verification")
+}
+
+/// Approved mentions of a post, loaded with HTMX so pages stay cacheable
+pub async fn list(
+    State(state): State<AppState>,
+    t: Translator,
+    Path(key): Path<String>,
+) -> Result<Html<String>, StatusCode> {
+    let published = state
+        .content
+        .read()
+        .await
+        .posts
+        .get(&key)
+        .is_some_and(|p| !p.metadata.draft);
+    if !state.config.webmentions || !published {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    let mentions = store::approved(&state.pool, &key).await.map_err(|e| {
+        tracing::error!("{:#}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let (mut likes, mut reposts, mut replies, mut others) =
+        (Vec::new(), Vec::new(), Vec::new(), Vec::new());
+    for mention in mentions {
+        match mention.kind.as_str() {
+            "like" => likes.push(mention),
+            "repost" => reposts.push(mention),
+            "reply" => replies.push(mention),
+            _ => others.push(mention),
+        }
+    }
+
+    let faces = [
+        ("webmentions-likes", likes),
+        ("webmentions-reposts", reposts),
+    ]
+    .into_iter()
+    .filter(|(_, group)| !group.is_empty())
+    .collect();
+
+    let template = WebmentionsTemplate {
+        t: &t,
+        faces,
+        replies,
+        mentions: others,
+    };
+    Ok(Html(
+        template
+            .render()
+            .unwrap_or_else(|e| format!("Error: {}", e)),
+    ))
+}
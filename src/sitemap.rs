@@ -0,0 +1,183 @@
+//! Sitemap XML (<https://www.sitemaps.org/protocol.html>) with hreflang
+//! alternates and image entries. Past [`MAX_URLS`] the URLs are split into
+//! numbered sitemaps listed by a sitemap index.
+
+use chrono::{DateTime, Utc};
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
+use quick_xml::events::BytesText;
+use quick_xml::Writer;
+
+/// Most URLs a single sitemap may list
+pub const MAX_URLS: usize = 50_000;
+
+/// Characters escaped in `<loc>`; non-ASCII is always escaped
+const URL_ESCAPE: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'<')
+    .add(b'>')
+    .add(b'`')
+    .add(b'{')
+    .add(b'}');
+
+#[derive(Debug, Clone)]
+pub struct SitemapUrl {
+    /// Absolute URL
+    pub loc: String,
+    pub lastmod: Option<DateTime<Utc>>,
+    pub changefreq: &'static str,
+    /// `(hreflang, absolute URL)` of every translation, including this one
+    pub alternates: Vec<(String, String)>,
+    /// Absolute image URLs shown on the page
+    pub images: Vec<String>,
+}
+
+impl SitemapUrl {
+    pub fn new(loc: String, changefreq: &'static str) -> Self {
+        Self {
+            loc,
+            lastmod: None,
+            changefreq,
+            alternates: Vec::new(),
+            images: Vec::new(),
+        }
+    }
+
+    pub fn lastmod(mut self, lastmod: Option<DateTime<Utc>>) -> Self {
+        self.lastmod = lastmod;
+        self
+    }
+
+    pub fn image(mut self, image: Option<String>) -> Self {
+        self.images.extend(image);
+        self
+    }
+}
+
+/// Number of numbered sitemaps needed for `urls` URLs; 0 when they fit in
+/// `/sitemap.xml` itself
+pub fn page_count(urls: usize) -> usize {
+    if urls <= MAX_URLS {
+        0
+    } else {
+        urls.div_ceil(MAX_URLS)
+    }
+}
+
+type XmlResult = Result<(), quick_xml::Error>;
+
+/// `<urlset>` document
+pub fn urlset(urls: &[SitemapUrl]) -> String {
+    write_document(|w| {
+        w.create_element("urlset")
+            .with_attribute(("xmlns", "http://www.sitemaps.org/schemas/sitemap/0.9"))
+            .with_attribute(("xmlns:xhtml", "http://www.w3.org/1999/xhtml"))
+            .with_attribute((
+                "xmlns:image",
+                "http://www.google.com/schemas/sitemap-image/1.1",
+            ))
+            .write_inner_content(|w| -> XmlResult {
+                for url in urls {
+                    w.create_element("url")
+                        .write_inner_content(|w| -> XmlResult {
+                            text_element(w, "loc", &escape_url(&url.loc))?;
+                            if let Some(lastmod) = url.lastmod {
+                                text_element(w, "lastmod", &format_date(lastmod))?;
+                            }
+                            text_element(w, "changefreq", url.changefreq)?;
+                            for (lang, href) in &url.alternates {
+                                w.create_element("xhtml:link")
+                                    .with_attribute(("rel", "alternate"))
+                                    .with_attribute(("hreflang", lang.as_str()))
+                                    .with_attribute(("href", escape_url(href).as_str()))
+                                    .write_empty()?;
+                            }
+                            for image in &url.images {
+                                w.create_element("image:image").write_inner_content(
+                                    |w| -> XmlResult {
+                                        text_element(w, "image:loc", &escape_url(image))
+                                    },
+                                )?;
+                            }
+                            Ok(())
+                        })?;
+                }
+                Ok(())
+            })?;
+        Ok(())
+    })
+}
+
+/// `<sitemapindex>` document listing `(absolute URL, lastmod)` of each sitemap
+pub fn index(sitemaps: &[(String, Option<DateTime<Utc>>)]) -> String {
+    write_document(|w| {
+        w.create_element("sitemapindex")
+            .with_attribute(("xmlns", "http://www.sitemaps.org/schemas/sitemap/0.9"))
+            .write_inner_content(|w| -> XmlResult {
+                for (loc, lastmod) in sitemaps {
+                    w.create_element("sitemap")
+                        .write_inner_content(|w| -> XmlResult {
+                            text_element(w, "loc", &escape_url(loc))?;
+                            if let Some(lastmod) = lastmod {
+                                text_element(w, "lastmod", &format_date(*lastmod))?;
+                            }
+                            Ok(())
+                        })?;
+                }
+                Ok(())
+            })?;
+        Ok(())
+    })
+}
+
+fn write_document(write: impl FnOnce(&mut Writer<Vec<u8>>) -> XmlResult) -> String {
+    let mut writer = Writer::new(Vec::new());
+    if let Err(e) = write(&mut writer) {
+        tracing::error!("Failed to write sitemap: {}", e);
+    }
+    let xml = String::from_utf8_lossy(&writer.into_inner()).into_owned();
+    format!(r#"<?xml version="1.0" encoding="UTF-8"?>{}"#, xml)
+}
+
+fn text_element(w: &mut Writer<Vec<u8>>, name: &str, text: &str) -> XmlResult {
+    w.create_element(name)
+        .write_text_content(BytesText::new(text))?;
+    Ok(())
+}
+
+/// Percent-encode non-ASCII and unsafe characters; XML escaping is left to
+/// the writer
+fn escape_url(url: &str) -> String {
+    utf8_percent_encode(url, URL_ESCAPE).to_string()
+}
+
+fn format_date(date: DateTime<Utc>) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_urlset_escapes_urls() {
+        let date = DateTime::parse_from_rfc3339("2025-03-01T10:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let url = SitemapUrl::new("https://example.com/blog/tag/c&c++".into(), "weekly")
+            .lastmod(Some(date))
+            .image(Some("https://example.com/обложка.webp".into()));
+
+        let xml = urlset(&[url]);
+        assert!(xml.contains("<loc>https://example.com/blog/tag/c&amp;c++</loc>"));
+        assert!(xml.contains("<lastmod>2025-03-01</lastmod>"));
+        assert!(xml.contains(
+            "<image:image><image:loc>https://example.com/%D0%BE%D0%B1%D0%BB%D0%BE%D0%B6%D0%BA%D0%B0.webp</image:loc></image:image>"
+        ));
+
+        assert_eq!(page_count(MAX_URLS), 0);
+        assert_eq!(page_count(MAX_URLS + 1), 2);
+        let xml = index(&[("https://example.com/sitemaps/1.xml".into(), None)]);
+        assert!(xml.contains("<sitemap><loc>https://example.com/sitemaps/1.xml</loc></sitemap>"));
+    }
+}
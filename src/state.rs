@@ -1,10 +1,19 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use redis::aio::ConnectionManager;
 use sqlx::PgPool;
 use tokio::sync::RwLock;
 
-use crate::{config::Config, content::ContentStore, email::EmailService};
+use crate::{
+    cache::PageCache,
+    config::Config,
+    content::ContentStore,
+    email::EmailService,
+    i18n::Catalog,
+    og::OgRenderer,
+    webmention::{HttpFetcher, WebmentionQueue},
+};
 
 /// Shared application state
 #[derive(Clone)]
@@ -14,6 +23,12 @@ pub struct AppState {
     pub config: Config,
     pub email: EmailService,
     pub redis: Option<ConnectionManager>,
+    pub catalog: Arc<Catalog>,
+    /// Rendered pages, cleared on content reload
+    pub page_cache: Arc<PageCache>,
+    pub og: Arc<OgRenderer>,
+    /// Webmentions waiting for verification
+    pub webmentions: WebmentionQueue,
 }
 
 impl AppState {
@@ -23,21 +38,44 @@ impl AppState {
         config: Config,
         email: EmailService,
         redis: Option<ConnectionManager>,
+        catalog: Catalog,
+        fetcher: HttpFetcher,
     ) -> Self {
+        let page_cache = PageCache::new(Duration::from_secs(config.page_cache_ttl));
+        let og = OgRenderer::new(&config);
+        let webmentions = WebmentionQueue::spawn(pool.clone(), fetcher);
         Self {
             pool,
             content: Arc::new(RwLock::new(content)),
             config,
             email,
             redis,
+            catalog: Arc::new(catalog),
+            page_cache: Arc::new(page_cache),
+            og: Arc::new(og),
+            webmentions,
         }
     }
 
     /// Reload content from filesystem
     pub async fn reload_content(&self) -> anyhow::Result<()> {
-        let new_content = ContentStore::load(&self.config.content_dir).await?;
+        let new_content =
+            ContentStore::load(&self.config.content_dir, &self.config.languages).await?;
         let mut content = self.content.write().await;
         *content = new_content;
+        self.page_cache.clear();
+        drop(content);
+        self.prune_og_cache().await;
         Ok(())
     }
+
+    /// Delete Open Graph cards that haven't been served for a while
+    pub async fn prune_og_cache(&self) {
+        let og = self.og.clone();
+        match tokio::task::spawn_blocking(move || og.prune()).await {
+            Ok(0) => {}
+            Ok(pruned) => tracing::info!("Pruned {} unused Open Graph images", pruned),
+            Err(e) => tracing::warn!("Failed to prune Open Graph images: {}", e),
+        }
+    }
 }
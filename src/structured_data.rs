@@ -0,0 +1,172 @@
+//! schema.org JSON-LD for rich results. Handlers build a [`Graph`] and pass
+//! [`Graph::to_json`] to their template; `base.html` puts it in `<head>`.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// Top-level `@graph` of schema.org nodes
+#[derive(Debug, Serialize)]
+pub struct Graph {
+    #[serde(rename = "@context")]
+    context: &'static str,
+    #[serde(rename = "@graph")]
+    nodes: Vec<Node>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "@type")]
+pub enum Node {
+    BlogPosting(BlogPosting),
+    SoftwareSourceCode(SoftwareSourceCode),
+    Person(Person),
+    BreadcrumbList(BreadcrumbList),
+}
+
+/// `author` and `publisher` values
+#[derive(Debug, Serialize)]
+#[serde(tag = "@type")]
+pub enum Agent {
+    Person(Person),
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Person {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub job_title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    /// Profile URLs on other sites
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub same_as: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlogPosting {
+    pub headline: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub url: String,
+    pub main_entity_of_page: String,
+    pub date_published: DateTime<Utc>,
+    pub date_modified: DateTime<Utc>,
+    pub in_language: String,
+    pub author: Vec<Agent>,
+    pub publisher: Agent,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub keywords: Vec<String>,
+    pub word_count: usize,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SoftwareSourceCode {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub url: String,
+    pub code_repository: String,
+    pub date_created: DateTime<Utc>,
+    pub date_modified: DateTime<Utc>,
+    pub author: Agent,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub keywords: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BreadcrumbList {
+    item_list_element: Vec<ListItem>,
+}
+
+#[derive(Debug, Serialize)]
+struct ListItem {
+    #[serde(rename = "@type")]
+    kind: &'static str,
+    position: usize,
+    name: String,
+    item: String,
+}
+
+impl BreadcrumbList {
+    /// `(name, absolute URL)` pairs from the home page down to the current page
+    pub fn new(crumbs: Vec<(String, String)>) -> Self {
+        Self {
+            item_list_element: crumbs
+                .into_iter()
+                .enumerate()
+                .map(|(i, (name, item))| ListItem {
+                    kind: "ListItem",
+                    position: i + 1,
+                    name,
+                    item,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Graph {
+    pub fn new(nodes: Vec<Node>) -> Self {
+        Self {
+            context: "https://schema.org",
+            nodes,
+        }
+    }
+
+    /// JSON safe to embed in a `<script>` element: `<`, `>` and `&` are
+    /// written as Unicode escapes, so content can't close the tag
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self)
+            .unwrap_or_default()
+            .replace('<', "\\u003c")
+            .replace('>', "\\u003e")
+            .replace('&', "\\u0026")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_graph_is_script_safe() {
+        let author = Person {
+            name: "Ann".into(),
+            url: Some("https://example.com".into()),
+            ..Person::default()
+        };
+        let graph = Graph::new(vec![
+            Node::Person(author),
+            Node::BreadcrumbList(BreadcrumbList::new(vec![
+                ("Home".into(), "https://example.com".into()),
+                (
+                    "</script><b>".into(),
+                    "https://example.com/blog?a=1&b=2".into(),
+                ),
+            ])),
+        ]);
+
+        let json = graph.to_json();
+        assert!(!json.contains('<') && !json.contains('&'));
+
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["@context"], "https://schema.org");
+        assert_eq!(value["@graph"][0]["@type"], "Person");
+        assert!(value["@graph"][0].get("jobTitle").is_none());
+        let crumb = &value["@graph"][1]["itemListElement"][1];
+        assert_eq!(crumb["@type"], "ListItem");
+        assert_eq!(crumb["position"], 2);
+        assert_eq!(crumb["name"], "</script><b>");
+    }
+}
@@ -0,0 +1,308 @@
+//! The small part of microformats2 (<https://microformats.org/wiki/microformats2>)
+//! needed to show a mention: the source's `h-entry`, what kind of response it
+//! is, its author `h-card`, content and publish date.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use scraper::{ElementRef, Html};
+use url::Url;
+
+use super::{MentionKind, MAX_URL_LEN};
+
+/// Longest mention content kept, in characters
+const MAX_CONTENT: usize = 500;
+/// Longest author name kept, the size of `webmentions.author_name`
+const MAX_NAME: usize = 255;
+
+/// Response properties, checked in order, that make an entry more than a
+/// plain mention
+const KIND_PROPERTIES: &[(&str, MentionKind)] = &[
+    ("u-like-of", MentionKind::Like),
+    ("u-repost-of", MentionKind::Repost),
+    ("u-in-reply-to", MentionKind::Reply),
+];
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Author {
+    pub name: Option<String>,
+    pub url: Option<String>,
+    pub photo: Option<String>,
+}
+
+/// What a verified source says about the target
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedMention {
+    pub kind: MentionKind,
+    pub author: Author,
+    /// The entry's own permalink, when it has one
+    pub url: Option<String>,
+    /// Plain text, truncated
+    pub content: Option<String>,
+    pub published: Option<DateTime<Utc>>,
+}
+
+impl ParsedMention {
+    /// A mention nothing more is known about, e.g. from a page without an `h-entry`
+    pub fn plain() -> Self {
+        Self {
+            kind: MentionKind::Mention,
+            author: Author::default(),
+            url: None,
+            content: None,
+            published: None,
+        }
+    }
+}
+
+/// Whether any `href` or `src` in the page points at `target`
+pub fn links_to(html: &Html, source: &Url, target: &Url) -> bool {
+    html.root_element()
+        .descendants()
+        .filter_map(ElementRef::wrap)
+        .any(|e| links_to_target(e, source, target))
+}
+
+/// Read the first `h-entry` that links to `target`, falling back to the first
+/// one on the page
+pub fn parse(html: &Html, source: &Url, target: &Url) -> ParsedMention {
+    let entries: Vec<ElementRef> = html
+        .root_element()
+        .descendants()
+        .filter_map(ElementRef::wrap)
+        .filter(|e| has_class(e, "h-entry"))
+        .collect();
+    let entry = entries
+        .iter()
+        .find(|entry| {
+            entry
+                .descendants()
+                .filter_map(ElementRef::wrap)
+                .any(|e| links_to_target(e, source, target))
+        })
+        .or(entries.first());
+    let Some(&entry) = entry else {
+        return ParsedMention::plain();
+    };
+
+    let kind = KIND_PROPERTIES
+        .iter()
+        .find(|(class, _)| {
+            properties(entry, class)
+                .filter_map(|p| url_value(p, source))
+                .any(|url| same_url(&url, target))
+        })
+        .map_or(MentionKind::Mention, |&(_, kind)| kind);
+
+    let content = match kind {
+        MentionKind::Like | MentionKind::Repost => None,
+        MentionKind::Reply | MentionKind::Mention => property(entry, "e-content")
+            .or_else(|| property(entry, "p-content"))
+            .or_else(|| property(entry, "p-summary"))
+            .map(text)
+            .filter(|t| !t.is_empty())
+            .map(|t| truncate(&t, MAX_CONTENT)),
+    };
+
+    ParsedMention {
+        kind,
+        author: author(entry, source),
+        url: property(entry, "u-url")
+            .and_then(|u| url_value(u, source))
+            .map(String::from),
+        content,
+        published: property(entry, "dt-published").and_then(date_value),
+    }
+}
+
+fn author(entry: ElementRef, base: &Url) -> Author {
+    let Some(card) = property(entry, "p-author").or_else(|| property(entry, "u-author")) else {
+        return Author::default();
+    };
+    if !is_root(&card) {
+        return Author {
+            name: name(text(card)),
+            url: url_value(card, base).map(String::from),
+            photo: None,
+        };
+    }
+
+    // Implied properties: a bare `<a class="h-card" href>` with a name inside
+    let photo = property(card, "u-photo").or_else(|| {
+        card.descendants()
+            .filter_map(ElementRef::wrap)
+            .find(|e| e.value().name() == "img")
+    });
+    Author {
+        name: name(property(card, "p-name").map_or_else(|| text(card), text)),
+        url: property(card, "u-url")
+            .and_then(|u| url_value(u, base))
+            .or_else(|| card.value().attr("href").and_then(|h| resolve(base, h)))
+            .map(String::from),
+        photo: photo.and_then(|p| url_value(p, base)).map(String::from),
+    }
+}
+
+fn links_to_target(element: ElementRef, source: &Url, target: &Url) -> bool {
+    ["href", "src"]
+        .iter()
+        .filter_map(|attr| element.value().attr(attr))
+        .filter_map(|href| resolve(source, href))
+        .any(|url| same_url(&url, target))
+}
+
+/// Properties named `class` that belong to `root` itself, not to a
+/// microformat nested inside it
+fn properties<'a>(root: ElementRef<'a>, class: &'a str) -> impl Iterator<Item = ElementRef<'a>> {
+    root.descendants()
+        .skip(1)
+        .filter_map(ElementRef::wrap)
+        .filter(move |e| {
+            has_class(e, class)
+                && e.ancestors()
+                    .filter_map(ElementRef::wrap)
+                    .find(is_root)
+                    .is_some_and(|owner| owner.id() == root.id())
+        })
+}
+
+fn property<'a>(root: ElementRef<'a>, class: &'a str) -> Option<ElementRef<'a>> {
+    properties(root, class).next()
+}
+
+fn has_class(element: &ElementRef, class: &str) -> bool {
+    element.value().classes().any(|c| c == class)
+}
+
+/// Microformat root elements have an `h-*` class
+fn is_root(element: &ElementRef) -> bool {
+    element.value().classes().any(|c| c.starts_with("h-"))
+}
+
+/// Value of a `u-*` property: its link, the `u-url` of a nested microformat,
+/// or its text
+fn url_value(element: ElementRef, base: &Url) -> Option<Url> {
+    let value = element.value();
+    if let Some(href) = value.attr("href").or_else(|| value.attr("src")) {
+        return resolve(base, href);
+    }
+    if is_root(&element) {
+        return property(element, "u-url").and_then(|u| url_value(u, base));
+    }
+    resolve(base, &text(element))
+}
+
+/// Value of a `dt-*` property: RFC 3339, or a bare date
+fn date_value(element: ElementRef) -> Option<DateTime<Utc>> {
+    let raw = element
+        .value()
+        .attr("datetime")
+        .map(str::to_string)
+        .unwrap_or_else(|| text(element));
+
+    DateTime::parse_from_rfc3339(&raw)
+        .map(|d| d.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            NaiveDate::parse_from_str(&raw, "%Y-%m-%d")
+                .ok()
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+                .map(|d| d.and_utc())
+        })
+}
+
+/// Absolute http(s) URL without a fragment; over-long ones are dropped
+fn resolve(base: &Url, href: &str) -> Option<Url> {
+    let mut url = base.join(href.trim()).ok()?;
+    url.set_fragment(None);
+    (matches!(url.scheme(), "http" | "https") && url.as_str().len() <= MAX_URL_LEN).then_some(url)
+}
+
+/// Author name, truncated; the implied name of an `h-card` is all its text
+fn name(text: String) -> Option<String> {
+    (!text.is_empty()).then(|| truncate(&text, MAX_NAME))
+}
+
+/// URLs are equal up to a trailing slash
+fn same_url(a: &Url, b: &Url) -> bool {
+    a.as_str().trim_end_matches('/') == b.as_str().trim_end_matches('/')
+}
+
+/// Text content with whitespace collapsed
+fn text(element: ElementRef) -> String {
+    element
+        .text()
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// At most `max` characters, the last one an ellipsis when cut
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(max - 1).collect();
+    format!("{}…", cut.trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_like_with_nested_card() {
+        let source = Url::parse("https://alice.example/likes/1").unwrap();
+        let target = Url::parse("https://example.com/blog/hello-world").unwrap();
+        let html = Html::parse_document(
+            r#"<div class="h-entry">
+                 <a class="p-author h-card" href="/">
+                   <img class="u-photo" src="/me.jpg" alt=""> Alice
+                 </a>
+                 liked <a class="u-like-of" href="https://example.com/blog/hello-world/">a post</a>
+                 <a class="u-url" href="/likes/1#top"><time class="dt-published" datetime="2025-03-01T10:00:00+02:00">March 1</time></a>
+                 <div class="e-content">Ignored for likes</div>
+               </div>"#,
+        );
+
+        assert!(links_to(&html, &source, &target));
+        let mention = parse(&html, &source, &target);
+        assert_eq!(mention.kind, MentionKind::Like);
+        assert_eq!(mention.content, None);
+        assert_eq!(
+            mention.author,
+            Author {
+                name: Some("Alice".into()),
+                url: Some("https://alice.example/".into()),
+                photo: Some("https://alice.example/me.jpg".into()),
+            }
+        );
+        assert_eq!(
+            mention.url.as_deref(),
+            Some("https://alice.example/likes/1")
+        );
+        assert_eq!(
+            mention.published.map(|d| d.to_rfc3339()),
+            Some("2025-03-01T08:00:00+00:00".into())
+        );
+    }
+
+    #[test]
+    fn test_long_values_fit_columns() {
+        let source = Url::parse("https://alice.example/").unwrap();
+        let target = Url::parse("https://example.com/blog/hello-world").unwrap();
+        let html = Html::parse_document(&format!(
+            r#"<div class="h-entry">
+                 <div class="p-author h-card">{}<img src="/{}.jpg"></div>
+                 <a href="https://example.com/blog/hello-world">post</a>
+               </div>"#,
+            "word ".repeat(200),
+            "a".repeat(MAX_URL_LEN),
+        ));
+
+        let author = parse(&html, &source, &target).author;
+        let name = author.name.unwrap();
+        assert_eq!(name.chars().count(), MAX_NAME);
+        assert!(name.ends_with('…'));
+        assert_eq!(author.photo, None);
+    }
+}
@@ -0,0 +1,470 @@
+//! Receiving Webmentions (<https://www.w3.org/TR/webmention/>).
+//!
+//! `POST /webmention` only checks that the target is one of our posts and
+//! queues the request. A background worker fetches the source, confirms it
+//! still links to the target and stores what its microformats say about it;
+//! sources that stopped linking or are gone have their mention deleted.
+//! Stored mentions stay hidden until approved from admin.
+
+pub mod microformats;
+pub mod store;
+
+use std::future::Future;
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context, Result};
+use axum::async_trait;
+use scraper::Html;
+use serde::Deserialize;
+use sqlx::PgPool;
+use tokio::sync::mpsc;
+use url::{Host, Url};
+
+use self::microformats::ParsedMention;
+use crate::VERSION;
+
+/// Requests waiting for verification before new ones are turned away
+const QUEUE_SIZE: usize = 256;
+/// Largest source page read
+const MAX_SOURCE_BYTES: usize = 1024 * 1024;
+const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+const MAX_REDIRECTS: usize = 5;
+/// Longest URL stored, the size of the URL columns in `webmentions`
+pub const MAX_URL_LEN: usize = 2048;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MentionKind {
+    Like,
+    Repost,
+    Reply,
+    Mention,
+}
+
+impl MentionKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MentionKind::Like => "like",
+            MentionKind::Repost => "repost",
+            MentionKind::Reply => "reply",
+            MentionKind::Mention => "mention",
+        }
+    }
+}
+
+/// Moderation state of a stored mention
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+impl Status {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Status::Pending => "pending",
+            Status::Approved => "approved",
+            Status::Rejected => "rejected",
+        }
+    }
+}
+
+/// Result of fetching a source
+#[derive(Debug)]
+pub enum Fetched {
+    Page {
+        content_type: String,
+        body: String,
+    },
+    /// 404 or 410: the source was deleted
+    Gone,
+}
+
+/// Fetches source pages; tests swap in a local stand-in
+#[async_trait]
+pub trait Fetcher: Send + Sync + 'static {
+    async fn fetch(&self, url: &Url) -> Result<Fetched>;
+}
+
+/// [`Fetcher`] over HTTP that only connects to public addresses. Host names
+/// are resolved by [`PublicResolver`], so a name pointing at a private
+/// network (or rebinding to one) fails like an unknown host, on the first
+/// request and on redirects alike.
+pub struct HttpFetcher {
+    client: reqwest::Client,
+}
+
+type LookupFuture = Pin<Box<dyn Future<Output = io::Result<Vec<SocketAddr>>> + Send>>;
+/// Host name lookup; the system resolver outside tests
+type Lookup = Arc<dyn Fn(String) -> LookupFuture + Send + Sync>;
+
+/// DNS resolver for the fetcher that drops private and loopback addresses
+struct PublicResolver {
+    lookup: Lookup,
+}
+
+impl reqwest::dns::Resolve for PublicResolver {
+    fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+        let lookup = self.lookup.clone();
+        let host = name.as_str().to_string();
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> = lookup(host.clone())
+                .await?
+                .into_iter()
+                .filter(|addr| is_public_ip(addr.ip()))
+                .collect();
+            if addrs.is_empty() {
+                return Err(format!("{} has no public address", host).into());
+            }
+            Ok(Box::new(addrs.into_iter()) as reqwest::dns::Addrs)
+        })
+    }
+}
+
+fn system_lookup(host: String) -> LookupFuture {
+    Box::pin(async move { Ok(tokio::net::lookup_host((host.as_str(), 0)).await?.collect()) })
+}
+
+impl HttpFetcher {
+    pub fn new() -> Result<Self> {
+        Self::with_lookup(Arc::new(system_lookup))
+    }
+
+    fn with_lookup(lookup: Lookup) -> Result<Self> {
+        let redirects = reqwest::redirect::Policy::custom(|attempt| {
+            if attempt.previous().len() >= MAX_REDIRECTS {
+                attempt.error("too many redirects")
+            } else if !is_public(attempt.url()) {
+                attempt.stop()
+            } else {
+                attempt.follow()
+            }
+        });
+        let client = reqwest::Client::builder()
+            .user_agent(format!("Nebula/{} (Webmention)", VERSION))
+            .timeout(FETCH_TIMEOUT)
+            .redirect(redirects)
+            .dns_resolver(Arc::new(PublicResolver { lookup }))
+            .build()
+            .context("Failed to build Webmention HTTP client")?;
+        Ok(Self { client })
+    }
+}
+
+#[async_trait]
+impl Fetcher for HttpFetcher {
+    async fn fetch(&self, url: &Url) -> Result<Fetched> {
+        let mut response = self
+            .client
+            .get(url.as_str())
+            .header(reqwest::header::ACCEPT, "text/html, */*;q=0.5")
+            .send()
+            .await
+            .with_context(|| format!("Failed to fetch {}", url))?;
+
+        let status = response.status();
+        if status == reqwest::StatusCode::NOT_FOUND || status == reqwest::StatusCode::GONE {
+            return Ok(Fetched::Gone);
+        }
+        if !status.is_success() {
+            bail!("{} returned {}", url, status);
+        }
+
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            body.extend_from_slice(&chunk);
+            if body.len() > MAX_SOURCE_BYTES {
+                bail!("{} is larger than {} bytes", url, MAX_SOURCE_BYTES);
+            }
+        }
+
+        Ok(Fetched::Page {
+            content_type,
+            body: String::from_utf8_lossy(&body).into_owned(),
+        })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Verification {
+    Verified(ParsedMention),
+    /// The source is gone or no longer links to the target
+    Unlinked,
+}
+
+/// Fetch `source` and check that it links to `target`
+pub async fn verify(fetcher: &dyn Fetcher, source: &Url, target: &Url) -> Result<Verification> {
+    let (content_type, body) = match fetcher.fetch(source).await? {
+        Fetched::Page { content_type, body } => (content_type, body),
+        Fetched::Gone => return Ok(Verification::Unlinked),
+    };
+
+    // Plain text and JSON sources only have to contain the URL
+    if !content_type.contains("html") {
+        return Ok(if body.contains(target.as_str()) {
+            Verification::Verified(ParsedMention::plain())
+        } else {
+            Verification::Unlinked
+        });
+    }
+
+    let html = Html::parse_document(&body);
+    if !microformats::links_to(&html, source, target) {
+        return Ok(Verification::Unlinked);
+    }
+    Ok(Verification::Verified(microformats::parse(
+        &html, source, target,
+    )))
+}
+
+/// An accepted request waiting for verification
+#[derive(Debug)]
+pub struct Job {
+    pub source: Url,
+    pub target: Url,
+    /// Content store key of the target post
+    pub post_key: String,
+}
+
+/// Sender side of the verification queue
+#[derive(Clone)]
+pub struct WebmentionQueue {
+    sender: mpsc::Sender<Job>,
+}
+
+impl WebmentionQueue {
+    /// Start the worker that verifies queued requests one at a time
+    pub fn spawn(pool: PgPool, fetcher: impl Fetcher) -> Self {
+        let (sender, mut receiver) = mpsc::channel::<Job>(QUEUE_SIZE);
+        tokio::spawn(async move {
+            while let Some(job) = receiver.recv().await {
+                if let Err(e) = process(&pool, &fetcher, &job).await {
+                    tracing::warn!(
+                        "Failed to verify Webmention from {} to {}: {:#}",
+                        job.source,
+                        job.target,
+                        e
+                    );
+                }
+            }
+        });
+        Self { sender }
+    }
+
+    /// Queue a request; fails when the queue is full
+    pub fn push(&self, job: Job) -> Result<()> {
+        self.sender
+            .try_send(job)
+            .map_err(|e| anyhow!("Webmention queue unavailable: {}", e))
+    }
+}
+
+async fn process(pool: &PgPool, fetcher: &dyn Fetcher, job: &Job) -> Result<()> {
+    match verify(fetcher, &job.source, &job.target).await? {
+        Verification::Verified(mention) => {
+            store::save(pool, job, &mention).await?;
+            tracing::info!(
+                "Verified {} from {} to {}",
+                mention.kind.as_str(),
+                job.source,
+                job.target
+            );
+        }
+        Verification::Unlinked => {
+            store::delete(pool, &job.source, &job.target).await?;
+            tracing::info!("{} no longer links to {}", job.source, job.target);
+        }
+    }
+    Ok(())
+}
+
+/// Parse a source or target URL: http(s) only, without the fragment and at
+/// most [`MAX_URL_LEN`] long
+pub fn parse_url(value: &str) -> Option<Url> {
+    let mut url = Url::parse(value.trim()).ok()?;
+    url.set_fragment(None);
+    (matches!(url.scheme(), "http" | "https")
+        && url.host().is_some()
+        && url.as_str().len() <= MAX_URL_LEN)
+        .then_some(url)
+}
+
+/// Whether a URL's host is on the public internet, as far as can be told
+/// without resolving it. Cheap check for requests; the fetcher's resolver
+/// checks the addresses it actually connects to.
+pub fn is_public(url: &Url) -> bool {
+    match url.host() {
+        Some(Host::Domain(domain)) => {
+            let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+            domain != "localhost" && !domain.ends_with(".localhost")
+        }
+        Some(Host::Ipv4(ip)) => is_public_ip(IpAddr::V4(ip)),
+        Some(Host::Ipv6(ip)) => is_public_ip(IpAddr::V6(ip)),
+        None => false,
+    }
+}
+
+fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_v4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(v4) => is_public_v4(v4),
+            None => is_public_v6(ip),
+        },
+    }
+}
+
+fn is_public_v4(ip: Ipv4Addr) -> bool {
+    let [a, b, c, _] = ip.octets();
+    !(ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        // "This network", 0.0.0.0/8
+        || a == 0
+        // Carrier-grade NAT, 100.64.0.0/10
+        || (a == 100 && b & 0xc0 == 64)
+        // IETF protocol assignments, 192.0.0.0/24
+        || (a == 192 && b == 0 && c == 0)
+        // Benchmarking, 198.18.0.0/15
+        || (a == 198 && b & 0xfe == 18)
+        // Reserved, 240.0.0.0/4
+        || a >= 240)
+}
+
+fn is_public_v6(ip: Ipv6Addr) -> bool {
+    let segments = ip.segments();
+    let first = segments[0];
+    !(ip.is_loopback()
+        || ip.is_unspecified()
+        // Unique local, fc00::/7, and link-local, fe80::/10
+        || first & 0xfe00 == 0xfc00
+        || first & 0xffc0 == 0xfe80
+        // IPv4-compatible, ::a.b.c.d
+        || segments[..6] == [0; 6]
+        // NAT64, 64:ff9b::/96
+        || segments[..6] == [0x64, 0xff9b, 0, 0, 0, 0])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Serves fixed pages instead of going over the network
+    struct StandIn(Vec<(&'static str, Fetched)>);
+
+    #[async_trait]
+    impl Fetcher for StandIn {
+        async fn fetch(&self, url: &Url) -> Result<Fetched> {
+            let page = self.0.iter().find(|(u, _)| *u == url.as_str());
+            Ok(match page {
+                Some((_, Fetched::Page { content_type, body })) => Fetched::Page {
+                    content_type: content_type.clone(),
+                    body: body.clone(),
+                },
+                _ => Fetched::Gone,
+            })
+        }
+    }
+
+    fn html(body: &str) -> Fetched {
+        Fetched::Page {
+            content_type: "text/html; charset=utf-8".into(),
+            body: body.into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_verify() {
+        let fetcher = StandIn(vec![
+            (
+                "https://bob.example/reply",
+                html(
+                    r#"<article class="h-entry">
+                         <span class="p-author h-card"><span class="p-name">Bob</span></span>
+                         <a class="u-in-reply-to" href="https://example.com/blog/hello-world">In reply to</a>
+                         <p class="e-content">Great <b>post</b>!</p>
+                       </article>"#,
+                ),
+            ),
+            (
+                "https://bob.example/unrelated",
+                html(r#"<a href="https://example.com/blog/other">Other</a>"#),
+            ),
+        ]);
+        let target = Url::parse("https://example.com/blog/hello-world").unwrap();
+        let verify_from = |source: &str| {
+            let source = Url::parse(source).unwrap();
+            let (fetcher, target) = (&fetcher, &target);
+            async move { verify(fetcher, &source, target).await.unwrap() }
+        };
+
+        let Verification::Verified(reply) = verify_from("https://bob.example/reply").await else {
+            panic!("reply was not verified");
+        };
+        assert_eq!(reply.kind, MentionKind::Reply);
+        assert_eq!(reply.author.name.as_deref(), Some("Bob"));
+        assert_eq!(reply.content.as_deref(), Some("Great post!"));
+
+        assert_eq!(
+            verify_from("https://bob.example/unrelated").await,
+            Verification::Unlinked
+        );
+        assert_eq!(
+            verify_from("https://bob.example/deleted").await,
+            Verification::Unlinked
+        );
+
+        assert!(!is_public(&parse_url("http://127.0.0.1:5432/").unwrap()));
+        assert!(!is_public(&parse_url("http://[::ffff:10.0.0.1]/").unwrap()));
+        assert!(!is_public(&parse_url("http://admin.localhost/").unwrap()));
+        for host in [
+            "0.1.2.3",
+            "192.0.0.8",
+            "198.19.0.1",
+            "240.0.0.1",
+            "[::7f00:1]",
+            "[64:ff9b::a00:1]",
+        ] {
+            let url = parse_url(&format!("http://{}/", host)).unwrap();
+            assert!(!is_public(&url), "{} is not public", host);
+        }
+        assert!(is_public(&parse_url("http://192.0.1.1/").unwrap()));
+        assert!(is_public(
+            &parse_url("https://bob.example/reply#c1").unwrap()
+        ));
+        assert_eq!(parse_url("ftp://bob.example/"), None);
+        let long = format!("https://bob.example/{}", "a".repeat(MAX_URL_LEN));
+        assert_eq!(parse_url(&long), None);
+    }
+
+    #[tokio::test]
+    async fn test_fetcher_refuses_private_addresses() {
+        // A host name that resolves to a local server, like an internal
+        // service or a DNS rebinding attack would
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local = listener.local_addr().unwrap();
+        let fetcher = HttpFetcher::with_lookup(Arc::new(move |_| {
+            Box::pin(async move { Ok(vec![local]) }) as LookupFuture
+        }))
+        .unwrap();
+
+        let url = Url::parse(&format!("http://intranet.example:{}/", local.port())).unwrap();
+        let error = fetcher.fetch(&url).await.unwrap_err();
+        assert!(format!("{:#}", error).contains("intranet.example has no public address"));
+
+        let accepted = tokio::time::timeout(Duration::from_millis(100), listener.accept()).await;
+        assert!(accepted.is_err(), "the fetcher connected to {}", local);
+    }
+}
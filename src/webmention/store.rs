@@ -0,0 +1,231 @@
+//! The `webmentions` table
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use url::Url;
+
+use super::microformats::ParsedMention;
+use super::{Job, Status};
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct Webmention {
+    pub id: i32,
+    pub source: String,
+    pub target: String,
+    pub post_key: String,
+    /// `like`, `repost`, `reply` or `mention`
+    pub kind: String,
+    pub author_name: Option<String>,
+    pub author_url: Option<String>,
+    pub author_photo: Option<String>,
+    /// Permalink of the source entry, when it has one
+    pub url: Option<String>,
+    pub content: Option<String>,
+    pub published: Option<DateTime<Utc>>,
+    /// `pending`, `approved` or `rejected`
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Webmention {
+    /// Author name, or the source's host for anonymous mentions
+    pub fn author(&self) -> String {
+        self.author_name.clone().unwrap_or_else(|| {
+            Url::parse(&self.source)
+                .ok()
+                .and_then(|u| u.host_str().map(String::from))
+                .unwrap_or_else(|| self.source.clone())
+        })
+    }
+
+    /// First letter of the author, shown when there is no photo
+    pub fn initial(&self) -> String {
+        self.author()
+            .chars()
+            .next()
+            .map(|c| c.to_uppercase().to_string())
+            .unwrap_or_default()
+    }
+
+    /// Where the mention links to on the other site
+    pub fn link(&self) -> &str {
+        self.url.as_deref().unwrap_or(&self.source)
+    }
+
+    pub fn date(&self) -> String {
+        self.published
+            .unwrap_or(self.created_at)
+            .format("%Y-%m-%d")
+            .to_string()
+    }
+}
+
+/// What a stored mention showed when it was moderated
+#[derive(Debug, sqlx::FromRow)]
+struct Moderated {
+    status: String,
+    author_name: Option<String>,
+    author_url: Option<String>,
+    author_photo: Option<String>,
+    content: Option<String>,
+}
+
+/// Status of a re-sent mention. An approved mention whose author or content
+/// changed goes back to `pending`, so edits at the source are moderated too;
+/// pending and rejected ones keep their status.
+fn status_after_update(stored: &Moderated, mention: &ParsedMention) -> String {
+    let changed = stored.author_name != mention.author.name
+        || stored.author_url != mention.author.url
+        || stored.author_photo != mention.author.photo
+        || stored.content != mention.content;
+    if changed && stored.status == Status::Approved.as_str() {
+        Status::Pending.as_str().to_string()
+    } else {
+        stored.status.clone()
+    }
+}
+
+/// Insert or refresh a verified mention
+pub async fn save(pool: &PgPool, job: &Job, mention: &ParsedMention) -> Result<()> {
+    let mut tx = pool.begin().await.context("Failed to save webmention")?;
+
+    let stored: Option<Moderated> = sqlx::query_as(
+        "SELECT status, author_name, author_url, author_photo, content FROM webmentions
+         WHERE source = $1 AND target = $2
+         FOR UPDATE",
+    )
+    .bind(job.source.as_str())
+    .bind(job.target.as_str())
+    .fetch_optional(&mut *tx)
+    .await
+    .context("Failed to load webmention")?;
+    let status = stored.map_or_else(
+        || Status::Pending.as_str().to_string(),
+        |stored| status_after_update(&stored, mention),
+    );
+
+    sqlx::query(
+        "INSERT INTO webmentions
+             (source, target, post_key, kind, author_name, author_url, author_photo,
+              url, content, published, status)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+         ON CONFLICT (source, target) DO UPDATE SET
+             post_key = EXCLUDED.post_key,
+             kind = EXCLUDED.kind,
+             author_name = EXCLUDED.author_name,
+             author_url = EXCLUDED.author_url,
+             author_photo = EXCLUDED.author_photo,
+             url = EXCLUDED.url,
+             content = EXCLUDED.content,
+             published = EXCLUDED.published,
+             status = EXCLUDED.status,
+             updated_at = NOW()",
+    )
+    .bind(job.source.as_str())
+    .bind(job.target.as_str())
+    .bind(&job.post_key)
+    .bind(mention.kind.as_str())
+    .bind(&mention.author.name)
+    .bind(&mention.author.url)
+    .bind(&mention.author.photo)
+    .bind(&mention.url)
+    .bind(&mention.content)
+    .bind(mention.published)
+    .bind(status)
+    .execute(&mut *tx)
+    .await
+    .context("Failed to save webmention")?;
+
+    tx.commit().await.context("Failed to save webmention")?;
+    Ok(())
+}
+
+pub async fn delete(pool: &PgPool, source: &Url, target: &Url) -> Result<()> {
+    sqlx::query("DELETE FROM webmentions WHERE source = $1 AND target = $2")
+        .bind(source.as_str())
+        .bind(target.as_str())
+        .execute(pool)
+        .await
+        .context("Failed to delete webmention")?;
+    Ok(())
+}
+
+/// Approved mentions of a post, oldest first
+pub async fn approved(pool: &PgPool, post_key: &str) -> Result<Vec<Webmention>> {
+    sqlx::query_as(
+        "SELECT * FROM webmentions
+         WHERE post_key = $1 AND status = 'approved'
+         ORDER BY COALESCE(published, created_at)",
+    )
+    .bind(post_key)
+    .fetch_all(pool)
+    .await
+    .context("Failed to load webmentions")
+}
+
+/// Mentions for moderation, newest first
+pub async fn list(pool: &PgPool, status: Option<Status>) -> Result<Vec<Webmention>> {
+    sqlx::query_as(
+        "SELECT * FROM webmentions
+         WHERE $1::TEXT IS NULL OR status = $1
+         ORDER BY created_at DESC",
+    )
+    .bind(status.map(|s| s.as_str()))
+    .fetch_all(pool)
+    .await
+    .context("Failed to list webmentions")
+}
+
+/// Returns false when there is no mention with this id
+pub async fn set_status(pool: &PgPool, id: i32, status: Status) -> Result<bool> {
+    let result =
+        sqlx::query("UPDATE webmentions SET status = $1, updated_at = NOW() WHERE id = $2")
+            .bind(status.as_str())
+            .bind(id)
+            .execute(pool)
+            .await
+            .context("Failed to update webmention")?;
+    Ok(result.rows_affected() > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::webmention::MentionKind;
+
+    #[test]
+    fn test_edited_mentions_are_moderated_again() {
+        let mut reply = ParsedMention::plain();
+        reply.kind = MentionKind::Reply;
+        reply.author.name = Some("Bob".into());
+        reply.content = Some("Great post!".into());
+        let stored = |status: &str| Moderated {
+            status: status.into(),
+            author_name: Some("Bob".into()),
+            author_url: None,
+            author_photo: None,
+            content: Some("Great post!".into()),
+        };
+
+        // Re-sent unchanged: approval stands
+        assert_eq!(status_after_update(&stored("approved"), &reply), "approved");
+
+        let mut edited = reply.clone();
+        edited.content = Some("Buy cheap watches".into());
+        assert_eq!(status_after_update(&stored("approved"), &edited), "pending");
+        assert_eq!(
+            status_after_update(&stored("rejected"), &edited),
+            "rejected"
+        );
+
+        let mut renamed = reply;
+        renamed.author.name = Some("Mallory".into());
+        assert_eq!(
+            status_after_update(&stored("approved"), &renamed),
+            "pending"
+        );
+    }
+}